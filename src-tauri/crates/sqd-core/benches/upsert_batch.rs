@@ -0,0 +1,70 @@
+//! upsert_batch 吞吐基准
+//!
+//! 运行：`cargo bench --manifest-path src-tauri/Cargo.toml -p sqd-core --bench upsert_batch`
+//! 可用环境变量 `UPSERT_BENCH_ROWS` 调整行数（默认 50000）。
+
+use std::time::Instant;
+
+use sqd_core::db::{establish_connection, Todo};
+use sqd_core::dto::ChangeSource;
+use sqd_core::services::TodoService;
+use sqd_core::Storage;
+
+fn make_rows(count: usize, updated_at: &str) -> Vec<Todo> {
+    (0..count)
+        .map(|i| Todo {
+            id: format!("00000000-0000-4000-8000-{:012}", i),
+            title: format!("基准任务 {}", i),
+            status: "pending".to_string(),
+            broker: format!("券商{}", i % 50),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            conclusion: None,
+            deleted_at: None,
+            restored_at: None,
+            restored_by: None,
+            version: 1,
+            series_id: None,
+            due_date: None,
+            items_total: 0,
+            items_done: 0,
+            custom_fields: "{}".to_string(),
+        })
+        .collect()
+}
+
+fn main() {
+    let rows: usize = std::env::var("UPSERT_BENCH_ROWS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(50_000);
+
+    let db_path = std::env::temp_dir().join(format!("sqd_upsert_bench_{}.db", std::process::id()));
+    let pool = establish_connection(db_path.to_str().expect("Invalid database path"));
+    pool.run_migrations().expect("Failed to run migrations");
+
+    let passes = [
+        ("insert", make_rows(rows, "2026-01-01T00:00:00Z")),
+        ("update", make_rows(rows, "2026-01-02T00:00:00Z")),
+        ("skip", make_rows(rows, "2026-01-02T00:00:00Z")),
+    ];
+
+    for (name, items) in passes {
+        let start = Instant::now();
+        let summary = TodoService::upsert_batch(&pool, items, ChangeSource::Sync).expect("upsert_batch failed");
+        let elapsed = start.elapsed();
+        println!(
+            "{:<6} rows={} inserted={} updated={} skipped={} elapsed={:.2?} throughput={:.0} rows/s",
+            name,
+            rows,
+            summary.inserted,
+            summary.updated,
+            summary.skipped,
+            elapsed,
+            rows as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    drop(pool);
+    let _ = std::fs::remove_file(&db_path);
+}
@@ -0,0 +1,7 @@
+-- 同步元数据（键值对）：已确认同步的游标、墓碑保留天数等
+CREATE TABLE sync_meta (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL
+);
+
+CREATE INDEX idx_todos_deleted_at ON todos (deleted_at);
@@ -0,0 +1,2 @@
+ALTER TABLE todos DROP COLUMN restored_by;
+ALTER TABLE todos DROP COLUMN restored_at;
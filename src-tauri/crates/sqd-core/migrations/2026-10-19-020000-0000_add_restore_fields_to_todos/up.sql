@@ -0,0 +1,3 @@
+-- 记录从回收站恢复的时间与操作人
+ALTER TABLE todos ADD COLUMN restored_at TEXT;
+ALTER TABLE todos ADD COLUMN restored_by TEXT;
@@ -0,0 +1,9 @@
+DELETE FROM sync_meta
+WHERE (key LIKE 'last_sync:%' OR key LIKE 'synced_until:%')
+  AND key NOT IN ('last_sync:default', 'synced_until:default');
+
+UPDATE sync_meta SET key = substr(key, 1, length(key) - length(':default'))
+WHERE key IN ('last_sync:default', 'synced_until:default');
+
+DROP INDEX IF EXISTS idx_todo_sync_profiles_profile;
+DROP TABLE todo_sync_profiles;
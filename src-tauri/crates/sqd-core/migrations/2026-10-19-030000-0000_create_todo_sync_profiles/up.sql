@@ -0,0 +1,15 @@
+-- 记录每条 Todo 属于哪个服务器配置，防止把一个服务器的数据推送到另一个服务器
+CREATE TABLE todo_sync_profiles (
+    todo_id TEXT PRIMARY KEY NOT NULL,
+    profile TEXT NOT NULL
+);
+
+CREATE INDEX idx_todo_sync_profiles_profile ON todo_sync_profiles (profile);
+
+-- 已有数据均来自旧版的单一服务器（迁移为 default 配置）
+INSERT INTO todo_sync_profiles (todo_id, profile)
+SELECT id, 'default' FROM todos;
+
+-- 同步游标改为按配置区分
+UPDATE sync_meta SET key = key || ':default'
+WHERE key IN ('last_sync', 'synced_until');
@@ -0,0 +1,2 @@
+-- 乐观并发控制：每次本地写入递增，更新时校验调用方看到的版本
+ALTER TABLE todos ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,18 @@
+-- 撤销/重做日志：记录每次 Todo 写操作前后的行快照，按会话（进程运行期）区分
+CREATE TABLE undo_journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    session_id TEXT NOT NULL,
+    todo_id TEXT NOT NULL,
+    -- create / update / delete
+    operation TEXT NOT NULL,
+    -- 操作前的行（JSON），create 为 NULL
+    before_state TEXT,
+    -- 操作后的行（JSON）
+    after_state TEXT NOT NULL,
+    -- 撤销或重做时要求的当前行版本，不一致说明已被其他写入修改
+    expected_version INTEGER NOT NULL,
+    undone BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_undo_journal_session ON undo_journal (session_id, undone, id);
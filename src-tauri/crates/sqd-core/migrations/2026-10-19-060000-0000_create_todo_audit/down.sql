@@ -0,0 +1,3 @@
+DROP TRIGGER todo_audit_no_delete;
+DROP TRIGGER todo_audit_no_update;
+DROP TABLE todo_audit;
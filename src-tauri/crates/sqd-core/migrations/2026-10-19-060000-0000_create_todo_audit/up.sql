@@ -0,0 +1,29 @@
+-- 审计日志：每次 Todo 写操作的前后快照，与写操作同一事务写入，只追加不修改
+CREATE TABLE todo_audit (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    todo_id TEXT NOT NULL,
+    -- create / update / delete / restore / undo / redo
+    operation TEXT NOT NULL,
+    -- ui / quick-add / sync / import / cli
+    source TEXT NOT NULL,
+    device_id TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    -- 操作前的行（JSON），新建时为 NULL
+    before_state TEXT,
+    -- 操作后的行（JSON）
+    after_state TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_todo_audit_todo ON todo_audit (todo_id, id);
+CREATE INDEX idx_todo_audit_created_at ON todo_audit (created_at);
+
+CREATE TRIGGER todo_audit_no_update BEFORE UPDATE ON todo_audit
+BEGIN
+    SELECT RAISE(ABORT, 'todo_audit is append-only');
+END;
+
+CREATE TRIGGER todo_audit_no_delete BEFORE DELETE ON todo_audit
+BEGIN
+    SELECT RAISE(ABORT, 'todo_audit is append-only');
+END;
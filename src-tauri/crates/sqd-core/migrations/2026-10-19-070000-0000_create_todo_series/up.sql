@@ -0,0 +1,19 @@
+-- 重复任务系列：按规则生成 Todo 实例（系列只保存在本地，不参与同步）
+CREATE TABLE todo_series (
+    id TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    broker TEXT NOT NULL,
+    -- 重复规则（JSON，见 RecurrenceRule）
+    rule TEXT NOT NULL,
+    -- 下一个待生成实例的日期（YYYY-MM-DD）
+    next_due TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 实例所属系列与到期日期
+ALTER TABLE todos ADD COLUMN series_id TEXT;
+ALTER TABLE todos ADD COLUMN due_date TEXT;
+
+CREATE INDEX idx_todos_series_id ON todos (series_id);
@@ -0,0 +1,11 @@
+-- 任务模板：一组按顺序排列的任务标题，实例化时为指定券商批量创建任务
+CREATE TABLE todo_templates (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    -- 实例化时任务的初始状态（pending / in_progress）
+    default_status TEXT NOT NULL DEFAULT 'pending',
+    -- 任务标题（JSON 字符串数组，按顺序），可含 {broker}、{date} 占位符
+    tasks TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,4 @@
+ALTER TABLE todos DROP COLUMN items_done;
+ALTER TABLE todos DROP COLUMN items_total;
+DROP INDEX idx_todo_items_todo_id;
+DROP TABLE todo_items;
@@ -0,0 +1,16 @@
+-- 任务的检查项（有序，仅本地不同步）
+CREATE TABLE todo_items (
+    id TEXT PRIMARY KEY NOT NULL,
+    todo_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    done BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_todo_items_todo_id ON todo_items (todo_id, position);
+
+-- 检查项进度（由检查项写操作维护，不参与同步）
+ALTER TABLE todos ADD COLUMN items_total INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE todos ADD COLUMN items_done INTEGER NOT NULL DEFAULT 0;
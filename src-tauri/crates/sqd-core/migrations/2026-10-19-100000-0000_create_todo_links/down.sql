@@ -0,0 +1,2 @@
+DROP INDEX idx_todo_links_target_id;
+DROP TABLE todo_links;
@@ -0,0 +1,12 @@
+-- 任务之间的关联（仅本地不同步）
+-- kind：blocks（source 阻塞 target）/ relates-to / duplicate-of（source 与 target 重复）
+CREATE TABLE todo_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    source_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (source_id, target_id, kind)
+);
+
+CREATE INDEX idx_todo_links_target_id ON todo_links (target_id);
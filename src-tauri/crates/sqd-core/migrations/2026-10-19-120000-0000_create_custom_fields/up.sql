@@ -0,0 +1,17 @@
+-- 自定义字段定义（按工作区的数据库保存，不参与同步）
+CREATE TABLE custom_fields (
+    id TEXT PRIMARY KEY NOT NULL,
+    -- 字段标识，任务中的值以它为键，创建后不可修改
+    key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    -- text / number / date / select
+    field_type TEXT NOT NULL,
+    -- select 字段的选项（JSON 数组），其他类型为空
+    options TEXT,
+    position INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 任务的字段值（JSON 对象，键为字段标识），随任务同步
+ALTER TABLE todos ADD COLUMN custom_fields TEXT NOT NULL DEFAULT '{}';
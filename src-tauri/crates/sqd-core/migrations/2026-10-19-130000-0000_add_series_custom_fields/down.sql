@@ -0,0 +1 @@
+ALTER TABLE todo_series DROP COLUMN custom_fields;
@@ -0,0 +1,2 @@
+-- 重复系列的自定义字段值（JSON 对象），生成实例时复制到任务
+ALTER TABLE todo_series ADD COLUMN custom_fields TEXT NOT NULL DEFAULT '{}';
@@ -6,10 +6,16 @@ pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
 /// 建立数据库连接池
 pub fn establish_connection(database_url: &str) -> DbPool {
+    try_establish_connection(database_url).expect("Failed to create pool.")
+}
+
+/// 建立数据库连接池，失败时返回错误（运行期打开新数据库时使用）
+pub fn try_establish_connection(database_url: &str) -> AppResult<DbPool> {
     let manager = ConnectionManager::<SqliteConnection>::new(database_url);
-    r2d2::Pool::builder()
-        .build(manager)
-        .expect("Failed to create pool.")
+    r2d2::Pool::builder().build(manager).map_err(|e| {
+        tracing::error!("Failed to create pool for {}: {}", database_url, e);
+        AppError::PoolError(format!("Failed to create pool: {}", e))
+    })
 }
 
 /// 从连接池获取数据库连接
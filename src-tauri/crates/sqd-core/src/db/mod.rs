@@ -0,0 +1,7 @@
+pub mod connection;
+pub mod models;
+pub mod schema;
+
+pub use connection::{establish_connection, get_connection, try_establish_connection, DbPool};
+pub use models::{AuditEntry, CustomField, JournalEntry, NewAuditEntry, NewJournalEntry, NewTodo, NewTodoLink, NewTodoTag, Tag, Todo, TodoItem, TodoLink, TodoSeries, TodoTemplate, UpdateTodo};
+pub use schema::{custom_fields, sync_meta, tags, todo_audit, todo_items, todo_links, todo_series, todo_sync_profiles, todo_tags, todo_templates, todos, undo_journal};
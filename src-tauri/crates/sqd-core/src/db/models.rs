@@ -0,0 +1,283 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::schema::{custom_fields, tags, todo_audit, todo_items, todo_links, todo_series, todo_tags, todo_templates, todos, undo_journal};
+
+fn default_version() -> i32 {
+    1
+}
+
+fn default_custom_fields() -> String {
+    "{}".to_string()
+}
+
+/// `custom_fields` 列保存 JSON 文本，序列化时展开为对象
+mod json_object {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::{Map, Value};
+
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_json::from_str::<Map<String, Value>>(value)
+            .unwrap_or_default()
+            .serialize(serializer)
+    }
+
+    /// 旧版同步数据没有该字段或为 null 时视为空对象
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let map = Option::<Map<String, Value>>::deserialize(deserializer)?.unwrap_or_default();
+        Ok(Value::Object(map).to_string())
+    }
+}
+
+#[derive(Debug, Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[diesel(table_name = todos)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Todo {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub broker: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub conclusion: Option<String>,
+    pub deleted_at: Option<String>,
+    pub restored_at: Option<String>,
+    pub restored_by: Option<String>,
+    /// 本地写入版本号（旧版同步数据缺省为 1）
+    #[serde(default = "default_version")]
+    pub version: i32,
+    /// 所属重复系列（见 `TodoSeries`，仅本地，同步时不推送也不覆盖）
+    #[serde(default)]
+    pub series_id: Option<String>,
+    /// 到期日期（YYYY-MM-DD），重复任务实例才有
+    #[serde(default)]
+    pub due_date: Option<String>,
+    /// 检查项总数与已完成数（仅本地，见 `TodoItem`）
+    #[serde(default)]
+    pub items_total: i32,
+    #[serde(default)]
+    pub items_done: i32,
+    /// 自定义字段值（字段标识 → 值，见 `CustomField`），随任务同步
+    #[serde(default = "default_custom_fields", with = "json_object")]
+    pub custom_fields: String,
+}
+
+#[derive(Debug, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = todos, treat_none_as_default_value = false)]
+pub struct NewTodo {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub broker: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub conclusion: Option<String>,
+    pub deleted_at: Option<String>,
+    pub restored_at: Option<String>,
+    pub restored_by: Option<String>,
+    /// 本地写入版本号（旧版同步数据缺省为 1）
+    #[serde(default = "default_version")]
+    pub version: i32,
+    /// 所属重复系列（见 `TodoSeries`）
+    #[serde(default)]
+    pub series_id: Option<String>,
+    /// 到期日期（YYYY-MM-DD），重复任务实例才有
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default = "default_custom_fields", with = "json_object")]
+    pub custom_fields: String,
+}
+
+impl From<Todo> for NewTodo {
+    fn from(todo: Todo) -> Self {
+        NewTodo {
+            id: todo.id,
+            title: todo.title,
+            status: todo.status,
+            broker: todo.broker,
+            created_at: todo.created_at,
+            updated_at: todo.updated_at,
+            conclusion: todo.conclusion,
+            deleted_at: todo.deleted_at,
+            restored_at: todo.restored_at,
+            restored_by: todo.restored_by,
+            version: todo.version,
+            series_id: todo.series_id,
+            due_date: todo.due_date,
+            custom_fields: todo.custom_fields,
+        }
+    }
+}
+
+#[derive(Debug, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = todos)]
+pub struct UpdateTodo {
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub broker: Option<String>,
+    pub updated_at: String,
+    pub conclusion: Option<String>,
+    pub deleted_at: Option<String>,
+    pub custom_fields: Option<String>,
+}
+
+/// 撤销日志中的一条记录
+#[derive(Debug, Queryable, Selectable, Clone)]
+#[diesel(table_name = undo_journal)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct JournalEntry {
+    pub id: i32,
+    pub session_id: String,
+    pub todo_id: String,
+    pub operation: String,
+    pub before_state: Option<String>,
+    pub after_state: String,
+    pub expected_version: i32,
+    pub undone: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = undo_journal)]
+pub struct NewJournalEntry {
+    pub session_id: String,
+    pub todo_id: String,
+    pub operation: String,
+    pub before_state: Option<String>,
+    pub after_state: String,
+    pub expected_version: i32,
+    pub created_at: String,
+}
+
+/// 审计日志中的一条记录（快照为 Todo 的 JSON）
+#[derive(Debug, Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = todo_audit)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct AuditEntry {
+    pub id: i32,
+    pub todo_id: String,
+    pub operation: String,
+    pub source: String,
+    pub device_id: String,
+    pub actor: String,
+    pub before_state: Option<String>,
+    pub after_state: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = todo_audit)]
+pub struct NewAuditEntry {
+    pub todo_id: String,
+    pub operation: String,
+    pub source: String,
+    pub device_id: String,
+    pub actor: String,
+    pub before_state: Option<String>,
+    pub after_state: String,
+    pub created_at: String,
+}
+
+/// 重复任务系列，`rule` 为 `RecurrenceRule` 的 JSON
+#[derive(Debug, Queryable, Selectable, Insertable, Serialize, Clone)]
+#[diesel(table_name = todo_series)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TodoSeries {
+    pub id: String,
+    pub title: String,
+    pub broker: String,
+    pub rule: String,
+    pub next_due: String,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    /// 自定义字段值（JSON 文本），生成实例时复制到任务
+    #[serde(with = "json_object")]
+    pub custom_fields: String,
+}
+
+/// 任务模板，`tasks` 为任务标题的 JSON 数组
+#[derive(Debug, Queryable, Selectable, Insertable, Serialize, Clone)]
+#[diesel(table_name = todo_templates)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TodoTemplate {
+    pub id: String,
+    pub name: String,
+    pub default_status: String,
+    pub tasks: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 任务的检查项，`position` 从 0 开始
+#[derive(Debug, Queryable, Selectable, Insertable, Serialize, Clone)]
+#[diesel(table_name = todo_items)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TodoItem {
+    pub id: String,
+    pub todo_id: String,
+    pub position: i32,
+    pub content: String,
+    pub done: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 任务关联，`kind` 为 blocks / relates-to / duplicate-of（见 `LinkKind`）
+#[derive(Debug, Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = todo_links)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TodoLink {
+    pub id: i32,
+    pub source_id: String,
+    pub target_id: String,
+    pub kind: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = todo_links)]
+pub struct NewTodoLink {
+    pub source_id: String,
+    pub target_id: String,
+    pub kind: String,
+    pub created_at: String,
+}
+
+/// 标签，名称不区分大小写唯一
+#[derive(Debug, Queryable, Selectable, Insertable, Serialize, Clone)]
+#[diesel(table_name = tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    /// 颜色（#RRGGBB）
+    pub color: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = todo_tags)]
+pub struct NewTodoTag {
+    pub todo_id: String,
+    pub tag_id: String,
+    pub created_at: String,
+}
+
+/// 自定义字段定义
+#[derive(Debug, Queryable, Selectable, Insertable, Clone)]
+#[diesel(table_name = custom_fields)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CustomField {
+    pub id: String,
+    /// 字段标识（任务中字段值的键），创建后不可修改
+    pub key: String,
+    pub name: String,
+    pub field_type: String,
+    /// select 字段的选项（JSON 数组）
+    pub options: Option<String>,
+    pub position: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
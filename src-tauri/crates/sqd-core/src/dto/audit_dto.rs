@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::AuditEntry;
+
+/// 写操作的来源，记录在审计日志中
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeSource {
+    /// 主窗口等界面操作（包括托盘、快捷键）
+    #[default]
+    Ui,
+    QuickAdd,
+    Sync,
+    Import,
+    Cli,
+    /// 重复任务按计划自动生成
+    Recurrence,
+}
+
+impl ChangeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSource::Ui => "ui",
+            ChangeSource::QuickAdd => "quick-add",
+            ChangeSource::Sync => "sync",
+            ChangeSource::Import => "import",
+            ChangeSource::Cli => "cli",
+            ChangeSource::Recurrence => "recurrence",
+        }
+    }
+}
+
+/// 获取任务历史的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoHistoryInput {
+    pub todo_id: String,
+    /// 页码，从 1 开始
+    #[serde(default)]
+    pub page: Option<i64>,
+    #[serde(default)]
+    pub page_size: Option<i64>,
+}
+
+/// 任务历史的一页（按时间倒序）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoHistoryPage {
+    pub items: Vec<AuditEntry>,
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
+}
+
+/// 导出审计日志的输入，时间为 ISO 8601 UTC，均可省略
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditExportInput {
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+    #[serde(default)]
+    pub todo_id: Option<String>,
+}
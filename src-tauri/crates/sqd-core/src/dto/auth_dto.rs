@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// POST /auth/login 请求体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+/// POST /auth/refresh 请求体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// 登录 / 刷新成功后返回的令牌
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// access token 有效期（秒）
+    pub expires_in: i64,
+}
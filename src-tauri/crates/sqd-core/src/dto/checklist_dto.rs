@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::{Todo, TodoItem};
+
+/// 添加检查项的输入（追加到末尾）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddTodoItemInput {
+    pub todo_id: String,
+    pub content: String,
+}
+
+/// 修改检查项的输入，未提供的字段保持不变
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTodoItemInput {
+    pub item_id: String,
+    pub content: Option<String>,
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTodoItemInput {
+    pub item_id: String,
+}
+
+/// 调整检查项顺序的输入，`item_ids` 必须恰好是该任务的全部检查项
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderTodoItemsInput {
+    pub todo_id: String,
+    pub item_ids: Vec<String>,
+}
+
+/// 任务（含最新进度）及其按顺序排列的检查项
+#[derive(Debug, Serialize)]
+pub struct Checklist {
+    pub todo: Todo,
+    pub items: Vec<TodoItem>,
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::CustomField;
+use crate::utils::{AppError, AppResult};
+
+/// 自定义字段类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    /// 文本，最多 500 字符
+    Text,
+    Number,
+    /// 日期（YYYY-MM-DD）
+    Date,
+    /// 从预设选项中选一个
+    Select,
+}
+
+impl FieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::Number => "number",
+            FieldType::Date => "date",
+            FieldType::Select => "select",
+        }
+    }
+
+    /// 解析数据库中保存的类型
+    pub fn parse_stored(value: &str) -> AppResult<Self> {
+        match value {
+            "text" => Ok(FieldType::Text),
+            "number" => Ok(FieldType::Number),
+            "date" => Ok(FieldType::Date),
+            "select" => Ok(FieldType::Select),
+            _ => Err(AppError::Validation(format!("未知的字段类型: {}", value))),
+        }
+    }
+}
+
+/// 创建自定义字段的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCustomFieldInput {
+    /// 字段标识（小写字母、数字、下划线），创建后不可修改
+    pub key: String,
+    pub name: String,
+    pub field_type: FieldType,
+    /// select 字段的选项
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// 修改自定义字段的输入，未提供的字段保持不变；标识与类型不可修改
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCustomFieldInput {
+    pub field_id: String,
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteCustomFieldInput {
+    pub field_id: String,
+}
+
+/// 按给定顺序重排全部字段
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderCustomFieldsInput {
+    pub field_ids: Vec<String>,
+}
+
+/// 返回给界面的字段定义（选项已解析）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldView {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    pub field_type: FieldType,
+    pub options: Vec<String>,
+    pub position: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl TryFrom<CustomField> for CustomFieldView {
+    type Error = AppError;
+
+    fn try_from(field: CustomField) -> AppResult<Self> {
+        let options = match field.options {
+            Some(options) => serde_json::from_str(&options)
+                .map_err(|e| AppError::Validation(format!("字段选项已损坏: {}", e)))?,
+            None => Vec::new(),
+        };
+        Ok(CustomFieldView {
+            field_type: FieldType::parse_stored(&field.field_type)?,
+            id: field.id,
+            key: field.key,
+            name: field.name,
+            options,
+            position: field.position,
+            created_at: field.created_at,
+            updated_at: field.updated_at,
+        })
+    }
+}
+
+/// 按一个自定义字段过滤任务，只使用与字段类型对应的条件；条件都为空时不过滤
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldFilter {
+    pub key: String,
+    /// 文本字段：包含（不区分大小写）
+    #[serde(default)]
+    pub contains: Option<String>,
+    /// 选择字段：等于
+    #[serde(default)]
+    pub equals: Option<String>,
+    /// 数字字段：范围（含两端）
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// 日期字段：范围（YYYY-MM-DD，含两端）
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::{Todo, TodoItem};
+use crate::utils::{AppError, AppResult};
+
+/// 任务关联类型
+///
+/// 数据库只保存 `blocks`、`relates-to`、`duplicate-of` 三种方向；
+/// `blocked-by` 与 `duplicated-by` 是从另一端看到的同一条关联。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkKind {
+    /// 本任务完成前对方不能开始
+    Blocks,
+    BlockedBy,
+    RelatesTo,
+    /// 本任务与对方重复
+    DuplicateOf,
+    DuplicatedBy,
+}
+
+impl LinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkKind::Blocks => "blocks",
+            LinkKind::BlockedBy => "blocked-by",
+            LinkKind::RelatesTo => "relates-to",
+            LinkKind::DuplicateOf => "duplicate-of",
+            LinkKind::DuplicatedBy => "duplicated-by",
+        }
+    }
+
+    /// 解析数据库中保存的类型
+    pub fn parse_stored(value: &str) -> AppResult<Self> {
+        match value {
+            "blocks" => Ok(LinkKind::Blocks),
+            "relates-to" => Ok(LinkKind::RelatesTo),
+            "duplicate-of" => Ok(LinkKind::DuplicateOf),
+            _ => Err(AppError::Validation(format!("未知的关联类型: {}", value))),
+        }
+    }
+
+    /// 转换为保存的方向，返回 (类型, source, target)
+    pub fn normalize<'a>(&self, this: &'a str, other: &'a str) -> (LinkKind, &'a str, &'a str) {
+        match self {
+            LinkKind::BlockedBy => (LinkKind::Blocks, other, this),
+            LinkKind::DuplicatedBy => (LinkKind::DuplicateOf, other, this),
+            kind => (*kind, this, other),
+        }
+    }
+
+    /// 从 target 一端看到的类型
+    pub fn inverse(&self) -> LinkKind {
+        match self {
+            LinkKind::Blocks => LinkKind::BlockedBy,
+            LinkKind::BlockedBy => LinkKind::Blocks,
+            LinkKind::RelatesTo => LinkKind::RelatesTo,
+            LinkKind::DuplicateOf => LinkKind::DuplicatedBy,
+            LinkKind::DuplicatedBy => LinkKind::DuplicateOf,
+        }
+    }
+}
+
+/// 创建关联的输入：`todo_id` 与 `other_id` 之间的 `kind` 关联（从 `todo_id` 看）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLinkInput {
+    pub todo_id: String,
+    pub other_id: String,
+    pub kind: LinkKind,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteLinkInput {
+    pub link_id: i32,
+}
+
+/// 从某个任务看到的一条关联
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedTodo {
+    pub link_id: i32,
+    pub kind: LinkKind,
+    pub todo: Todo,
+}
+
+/// 任务详情：任务、检查项与关联任务（不含回收站中的任务）
+#[derive(Debug, Serialize)]
+pub struct TodoDetail {
+    pub todo: Todo,
+    pub items: Vec<TodoItem>,
+    pub links: Vec<LinkedTodo>,
+}
@@ -0,0 +1,21 @@
+pub mod todo_dto;
+pub mod sync_dto;
+pub mod auth_dto;
+pub mod audit_dto;
+pub mod recurrence_dto;
+pub mod template_dto;
+pub mod checklist_dto;
+pub mod link_dto;
+pub mod tag_dto;
+pub mod custom_field_dto;
+
+pub use todo_dto::*;
+pub use sync_dto::*;
+pub use auth_dto::*;
+pub use audit_dto::*;
+pub use recurrence_dto::*;
+pub use template_dto::*;
+pub use checklist_dto::*;
+pub use link_dto::*;
+pub use tag_dto::*;
+pub use custom_field_dto::*;
@@ -0,0 +1,240 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::TodoSeries;
+use crate::utils::{AppError, AppResult};
+
+/// 日期格式（到期日期、系列下次生成日期）
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const MAX_DAILY_INTERVAL: u32 = 365;
+
+/// 重复规则（RRULE 的一个子集）
+///
+/// JSON 形如 `{"freq":"daily","interval":1}`、`{"freq":"weekly","weekdays":[1,5]}`、
+/// `{"freq":"monthlyDay","day":15}`、`{"freq":"monthlyLastWorkingDay"}`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "freq", rename_all = "camelCase")]
+pub enum RecurrenceRule {
+    /// 每 `interval` 天
+    Daily { interval: u32 },
+    /// 每周的指定几天，1 = 周一 … 7 = 周日
+    Weekly { weekdays: Vec<u8> },
+    /// 每月第 `day` 天，超过当月天数时取月末
+    MonthlyDay { day: u32 },
+    /// 每月最后一个工作日（周一至周五，不含节假日）
+    MonthlyLastWorkingDay,
+}
+
+fn is_working_day(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn last_of_month(date: NaiveDate) -> NaiveDate {
+    let next_month = first_of_month(date) + Months::new(1);
+    next_month.pred_opt().unwrap_or(date)
+}
+
+impl RecurrenceRule {
+    pub fn validate(&self) -> AppResult<()> {
+        match self {
+            RecurrenceRule::Daily { interval } if !(1..=MAX_DAILY_INTERVAL).contains(interval) => Err(
+                AppError::Validation(format!("重复间隔必须在 1-{} 天之间", MAX_DAILY_INTERVAL)),
+            ),
+            RecurrenceRule::Weekly { weekdays } if weekdays.is_empty() || weekdays.iter().any(|d| !(1..=7).contains(d)) => {
+                Err(AppError::Validation("每周重复需选择周一至周日中的至少一天".to_string()))
+            }
+            RecurrenceRule::MonthlyDay { day } if !(1..=31).contains(day) => {
+                Err(AppError::Validation("每月重复的日期必须在 1-31 之间".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 本月的发生日期（仅按月规则）
+    fn in_month(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            RecurrenceRule::MonthlyDay { day } => {
+                let last = last_of_month(date);
+                Some(last.with_day((*day).min(last.day())).unwrap_or(last))
+            }
+            RecurrenceRule::MonthlyLastWorkingDay => {
+                let mut candidate = last_of_month(date);
+                while !is_working_day(candidate) {
+                    candidate = candidate.pred_opt()?;
+                }
+                Some(candidate)
+            }
+            _ => None,
+        }
+    }
+
+    /// `date` 当天或之后的第一次发生日期
+    pub fn first_on_or_after(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            RecurrenceRule::Daily { .. } => date,
+            RecurrenceRule::Weekly { weekdays } => (0..7)
+                .map(|offset| date + Days::new(offset))
+                .find(|d| weekdays.contains(&(d.weekday().number_from_monday() as u8)))
+                .unwrap_or(date),
+            RecurrenceRule::MonthlyDay { .. } | RecurrenceRule::MonthlyLastWorkingDay => {
+                match self.in_month(date) {
+                    Some(this_month) if this_month >= date => this_month,
+                    _ => self
+                        .in_month(first_of_month(date) + Months::new(1))
+                        .unwrap_or(date),
+                }
+            }
+        }
+    }
+
+    /// 严格晚于 `date` 的下一次发生日期
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            RecurrenceRule::Daily { interval } => date + Days::new(u64::from(*interval)),
+            _ => self.first_on_or_after(date + Days::new(1)),
+        }
+    }
+}
+
+pub fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .map_err(|_| AppError::Validation(format!("日期格式应为 YYYY-MM-DD: {}", value)))
+}
+
+/// 创建重复任务系列的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSeriesInput {
+    pub title: String,
+    pub broker: String,
+    pub rule: RecurrenceRule,
+    /// 从哪天开始（YYYY-MM-DD），默认今天
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// 自定义字段值（字段标识 → 值），每个实例都带上
+    #[serde(default)]
+    pub custom_fields: Option<Map<String, Value>>,
+}
+
+/// 修改系列的输入，未提供的字段保持不变；标题和券商同时应用到未完成的实例
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSeriesInput {
+    pub series_id: String,
+    pub title: Option<String>,
+    pub broker: Option<String>,
+    pub rule: Option<RecurrenceRule>,
+    /// 要修改的自定义字段值，同时应用到未完成的实例；值为 null 时清除
+    #[serde(default)]
+    pub custom_fields: Option<Map<String, Value>>,
+}
+
+/// 停止系列的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopSeriesInput {
+    pub series_id: String,
+    /// 同时把未完成的实例移入回收站
+    #[serde(default)]
+    pub delete_open: bool,
+}
+
+/// 返回给界面的系列（规则已解析）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesView {
+    pub id: String,
+    pub title: String,
+    pub broker: String,
+    pub rule: RecurrenceRule,
+    pub next_due: String,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub custom_fields: Map<String, Value>,
+}
+
+impl TryFrom<TodoSeries> for SeriesView {
+    type Error = AppError;
+
+    fn try_from(series: TodoSeries) -> AppResult<Self> {
+        let rule = serde_json::from_str(&series.rule)
+            .map_err(|e| AppError::Validation(format!("重复规则已损坏: {}", e)))?;
+        Ok(SeriesView {
+            id: series.id,
+            title: series.title,
+            broker: series.broker,
+            rule,
+            next_due: series.next_due,
+            active: series.active,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+            custom_fields: serde_json::from_str(&series.custom_fields).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    #[test]
+    fn monthly_day_clamps_to_month_end() {
+        let rule = RecurrenceRule::MonthlyDay { day: 31 };
+        assert_eq!(rule.next_after(date("2026-01-31")), date("2026-02-28"));
+        assert_eq!(rule.next_after(date("2026-02-28")), date("2026-03-31"));
+        assert_eq!(rule.next_after(date("2026-04-15")), date("2026-04-30"));
+    }
+
+    #[test]
+    fn monthly_day_uses_leap_day() {
+        let rule = RecurrenceRule::MonthlyDay { day: 30 };
+        assert_eq!(rule.next_after(date("2028-01-30")), date("2028-02-29"));
+        assert_eq!(rule.first_on_or_after(date("2028-02-29")), date("2028-02-29"));
+        assert_eq!(
+            RecurrenceRule::MonthlyDay { day: 29 }.next_after(date("2027-01-29")),
+            date("2027-02-28")
+        );
+    }
+
+    #[test]
+    fn monthly_last_working_day_skips_weekends() {
+        let rule = RecurrenceRule::MonthlyLastWorkingDay;
+        // 2026-01-31 是周六
+        assert_eq!(rule.first_on_or_after(date("2026-01-01")), date("2026-01-30"));
+        // 2026-05-31 是周日
+        assert_eq!(rule.next_after(date("2026-04-30")), date("2026-05-29"));
+        // 当月的已过，取下月
+        assert_eq!(rule.next_after(date("2026-01-30")), date("2026-02-27"));
+    }
+
+    #[test]
+    fn weekly_and_daily_rules() {
+        let weekly = RecurrenceRule::Weekly { weekdays: vec![1, 5] };
+        // 2026-10-16 是周五
+        assert_eq!(weekly.next_after(date("2026-10-16")), date("2026-10-19"));
+        assert_eq!(weekly.first_on_or_after(date("2026-10-16")), date("2026-10-16"));
+
+        let daily = RecurrenceRule::Daily { interval: 3 };
+        assert_eq!(daily.next_after(date("2028-02-27")), date("2028-03-01"));
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_rules() {
+        assert!(RecurrenceRule::Daily { interval: 0 }.validate().is_err());
+        assert!(RecurrenceRule::Weekly { weekdays: vec![] }.validate().is_err());
+        assert!(RecurrenceRule::Weekly { weekdays: vec![8] }.validate().is_err());
+        assert!(RecurrenceRule::MonthlyDay { day: 32 }.validate().is_err());
+        assert!(RecurrenceRule::MonthlyDay { day: 31 }.validate().is_ok());
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use super::UpsertSummary;
+use crate::db::Todo;
+
+/// 墓碑清理结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeReport {
+    pub retention_days: u32,
+    pub cutoff: String,
+    /// 已确认过同步的服务器配置
+    pub synced_profiles: Vec<String>,
+    pub purged_ids: Vec<String>,
+}
+
+/// POST /sync 请求体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncRequest {
+    pub last_sync: Option<String>,
+    #[serde(default)]
+    pub changes: Vec<Todo>,
+}
+
+/// POST /sync 响应体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncResponse {
+    pub server_time: String,
+    pub changes: Vec<Todo>,
+}
+
+/// 一次同步的结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: UpsertSummary,
+    pub server_time: String,
+}
+
+/// GET /health 响应体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub version: String,
+    #[serde(default)]
+    pub auth_required: bool,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// 创建标签的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagInput {
+    pub name: String,
+    /// 颜色（#RRGGBB）
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 修改标签的输入，未提供的字段保持不变；`color` 为空字符串时清除颜色
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagInput {
+    pub tag_id: String,
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTagInput {
+    pub tag_id: String,
+}
+
+/// 把若干标签合并到目标标签：任务改挂目标标签，源标签删除
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTagsInput {
+    pub source_ids: Vec<String>,
+    pub target_id: String,
+}
+
+/// 设置任务的全部标签（替换原有标签）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTodoTagsInput {
+    pub todo_id: String,
+    pub tag_ids: Vec<String>,
+}
+
+/// 返回给界面的标签（含未删除任务数）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagView {
+    pub id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub todo_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
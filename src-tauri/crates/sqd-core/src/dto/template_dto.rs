@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::TodoTemplate;
+use crate::utils::{AppError, AppResult};
+
+/// 模板中可用的占位符
+pub const PLACEHOLDER_BROKER: &str = "{broker}";
+pub const PLACEHOLDER_DATE: &str = "{date}";
+
+/// 导出 JSON 的格式版本
+pub const TEMPLATE_EXPORT_VERSION: u32 = 1;
+
+/// 创建模板的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTemplateInput {
+    pub name: String,
+    /// 任务初始状态，默认 pending
+    #[serde(default)]
+    pub default_status: Option<String>,
+    /// 按顺序的任务标题，可含 `{broker}`、`{date}`
+    pub tasks: Vec<String>,
+}
+
+/// 修改模板的输入，未提供的字段保持不变
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTemplateInput {
+    pub template_id: String,
+    pub name: Option<String>,
+    pub default_status: Option<String>,
+    pub tasks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTemplateInput {
+    pub template_id: String,
+}
+
+/// 按模板为券商批量创建任务的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateInput {
+    pub template_id: String,
+    pub broker: String,
+}
+
+/// 返回给界面的模板（任务列表已解析）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateView {
+    pub id: String,
+    pub name: String,
+    pub default_status: String,
+    pub tasks: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl TryFrom<TodoTemplate> for TemplateView {
+    type Error = AppError;
+
+    fn try_from(template: TodoTemplate) -> AppResult<Self> {
+        let tasks = serde_json::from_str(&template.tasks)
+            .map_err(|e| AppError::Validation(format!("模板任务列表已损坏: {}", e)))?;
+        Ok(TemplateView {
+            id: template.id,
+            name: template.name,
+            default_status: template.default_status,
+            tasks,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        })
+    }
+}
+
+/// 导出文件中的一个模板（不含 id 与时间，便于在其他设备导入）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateExportItem {
+    pub name: String,
+    pub default_status: String,
+    pub tasks: Vec<String>,
+}
+
+/// 模板导出文件
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub templates: Vec<TemplateExportItem>,
+}
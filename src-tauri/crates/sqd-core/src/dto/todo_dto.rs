@@ -0,0 +1,204 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::Todo;
+use crate::dto::FieldFilter;
+use crate::utils::AppError;
+
+/// 验证状态值
+fn validate_status<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let status = String::deserialize(deserializer)?;
+    match status.as_str() {
+        "pending" | "in_progress" | "completed" => Ok(status),
+        _ => Err(serde::de::Error::custom(
+            "Invalid status. Must be one of: pending, in_progress, completed"
+        )),
+    }
+}
+
+/// 创建 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTodoInput {
+    pub title: String,
+    #[serde(deserialize_with = "validate_status")]
+    pub status: String,
+    pub broker: String,
+    pub conclusion: Option<String>,
+    /// 自定义字段值（字段标识 → 值）
+    #[serde(default)]
+    pub custom_fields: Option<Map<String, Value>>,
+}
+
+/// 更新 Todo 的输入 DTO
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTodoInput {
+    pub todo_id: String,
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "validate_optional_status")]
+    pub status: Option<String>,
+    pub broker: Option<String>,
+    pub conclusion: Option<String>,
+    /// 要修改的自定义字段值，未提供的字段保持不变，值为 null 时清除
+    #[serde(default)]
+    pub custom_fields: Option<Map<String, Value>>,
+    /// 调用方看到的版本号；提供时若已被其他写入修改则返回 `AppError::Conflict`
+    #[serde(default)]
+    pub expected_version: Option<i32>,
+}
+
+/// 验证可选的状态值
+fn validate_optional_status<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let opt: Option<String> = Option::deserialize(deserializer)?;
+    if let Some(status) = opt {
+        match status.as_str() {
+            "pending" | "in_progress" | "completed" => Ok(Some(status)),
+            _ => Err(serde::de::Error::custom(
+                "Invalid status. Must be one of: pending, in_progress, completed"
+            )),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// 批量更新的修改内容，未提供的字段保持不变
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoPatch {
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "validate_optional_status")]
+    pub status: Option<String>,
+    pub broker: Option<String>,
+    pub conclusion: Option<String>,
+}
+
+/// 批量更新 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateTodosInput {
+    pub todo_ids: Vec<String>,
+    pub patch: TodoPatch,
+}
+
+/// 批量删除 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDeleteTodosInput {
+    pub todo_ids: Vec<String>,
+}
+
+/// 批量操作中单个任务的结果：成功时带更新后的行，失败时带错误消息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub todo_id: String,
+    pub todo: Option<Todo>,
+    pub error: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn ok(todo: Todo) -> Self {
+        BulkItemResult { todo_id: todo.id.clone(), todo: Some(todo), error: None }
+    }
+
+    pub fn failed(todo_id: String, error: &AppError) -> Self {
+        let error = match error {
+            AppError::Database(diesel::result::Error::NotFound) => "任务不存在".to_string(),
+            other => other.to_string(),
+        };
+        BulkItemResult { todo_id, todo: None, error: Some(error) }
+    }
+}
+
+/// 删除 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTodoInput {
+    pub todo_id: String,
+}
+
+/// 恢复 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreTodoInput {
+    pub todo_id: String,
+    pub restored_by: Option<String>,
+}
+
+/// 搜索 Todo 的输入 DTO
+#[derive(Debug, Deserialize)]
+pub struct SearchTodoInput {
+    pub query: String,
+    /// 只返回带有其中任一标签的任务，为空时不按标签过滤
+    #[serde(default)]
+    pub tag_ids: Vec<String>,
+    /// 按自定义字段过滤
+    #[serde(default)]
+    pub field_filter: Option<FieldFilter>,
+}
+
+/// 获取任务列表的输入 DTO，条件都为空时返回全部未删除任务
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTodosInput {
+    /// 只返回带有其中任一标签的任务
+    #[serde(default)]
+    pub tag_ids: Vec<String>,
+    /// 按自定义字段过滤
+    #[serde(default)]
+    pub field_filter: Option<FieldFilter>,
+}
+
+/// 清空回收站的结果
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmptyTrashReport {
+    pub purged_ids: Vec<String>,
+    /// 配置了同步但删除尚未推送到服务端而保留的任务，下次同步后可再清空
+    pub kept_ids: Vec<String>,
+}
+
+/// 批量 upsert 的结果统计
+#[derive(Debug, Default, Serialize)]
+pub struct UpsertSummary {
+    pub inserted: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+/// 一次写操作提交后的任务变更，供界面就地更新
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TodosChanged {
+    /// 新增的行
+    pub created: Vec<Todo>,
+    /// 内容变化的行（包括从回收站恢复）
+    pub updated: Vec<Todo>,
+    /// 被删除（移入回收站）的行 id
+    pub deleted: Vec<String>,
+}
+
+impl TodosChanged {
+    pub fn created(todo: Todo) -> Self {
+        TodosChanged { created: vec![todo], ..Default::default() }
+    }
+
+    pub fn updated(todo: Todo) -> Self {
+        TodosChanged { updated: vec![todo], ..Default::default() }
+    }
+
+    pub fn deleted(id: String) -> Self {
+        TodosChanged { deleted: vec![id], ..Default::default() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
@@ -0,0 +1,18 @@
+//! SQD 工作助手核心业务（不依赖 Tauri）
+//!
+//! 桌面端、同步服务等共用：模型、DTO、校验、业务服务、存储抽象与数据库迁移。
+
+pub mod db;
+pub mod dto;
+pub mod services;
+pub mod storage;
+pub mod utils;
+
+#[cfg(test)]
+mod test_support;
+
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+
+pub use storage::Storage;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
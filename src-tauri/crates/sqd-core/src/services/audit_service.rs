@@ -0,0 +1,173 @@
+use crate::db::{todo_audit, AuditEntry, NewAuditEntry, Todo};
+use crate::dto::{AuditExportInput, ChangeSource, TodoHistoryInput, TodoHistoryPage};
+use crate::services::SyncService;
+use crate::storage::Storage;
+use crate::utils::{current_actor, AppError, AppResult};
+use chrono::{SecondsFormat, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// 本机设备标识（存于 `sync_meta`，首次写审计日志时生成）
+const KEY_DEVICE_ID: &str = "device_id";
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+/// 每条多行 INSERT 的行数（9 列 × 500 行）
+const INSERT_CHUNK_SIZE: usize = 500;
+
+pub const OP_RESTORE: &str = "restore";
+pub const OP_UNDO: &str = "undo";
+pub const OP_REDO: &str = "redo";
+
+const CSV_HEADER: &str =
+    "id,todo_id,operation,source,device_id,actor,created_at,before_state,after_state";
+
+fn to_json(todo: &Todo) -> AppResult<String> {
+    serde_json::to_string(todo)
+        .map_err(|e| AppError::Validation(format!("无法序列化审计记录: {}", e)))
+}
+
+/// CSV 字段转义：含逗号、引号或换行时加引号，引号加倍
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 一次写操作的审计上下文：来源、设备、操作人与时间在同一批记录中共享
+pub(crate) struct AuditWriter {
+    source: ChangeSource,
+    device_id: String,
+    actor: String,
+    created_at: String,
+}
+
+impl AuditWriter {
+    pub(crate) fn new(conn: &mut SqliteConnection, source: ChangeSource) -> AppResult<Self> {
+        Ok(AuditWriter {
+            source,
+            device_id: AuditService::device_id(conn)?,
+            actor: current_actor(),
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        })
+    }
+
+    pub(crate) fn entry(&self, operation: &str, before: Option<&Todo>, after: &Todo) -> AppResult<NewAuditEntry> {
+        Ok(NewAuditEntry {
+            todo_id: after.id.clone(),
+            operation: operation.to_string(),
+            source: self.source.as_str().to_string(),
+            device_id: self.device_id.clone(),
+            actor: self.actor.clone(),
+            before_state: before.map(to_json).transpose()?,
+            after_state: to_json(after)?,
+            created_at: self.created_at.clone(),
+        })
+    }
+
+    /// 写入一条审计记录，需与写操作在同一事务内调用
+    pub(crate) fn record(
+        &self,
+        conn: &mut SqliteConnection,
+        operation: &str,
+        before: Option<&Todo>,
+        after: &Todo,
+    ) -> AppResult<()> {
+        self.insert(conn, vec![self.entry(operation, before, after)?])
+    }
+
+    pub(crate) fn insert(&self, conn: &mut SqliteConnection, entries: Vec<NewAuditEntry>) -> AppResult<()> {
+        for chunk in entries.chunks(INSERT_CHUNK_SIZE) {
+            diesel::insert_into(todo_audit::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        Ok(())
+    }
+}
+
+/// 审计日志服务（只追加，数据库触发器禁止修改和删除）
+pub struct AuditService;
+
+impl AuditService {
+    /// 本机设备标识，不存在时生成并保存
+    pub fn device_id(conn: &mut SqliteConnection) -> AppResult<String> {
+        if let Some(id) = SyncService::get_meta(conn, KEY_DEVICE_ID)? {
+            return Ok(id);
+        }
+        let id = Uuid::new_v4().to_string();
+        SyncService::set_meta(conn, KEY_DEVICE_ID, &id)?;
+        tracing::info!("Generated device id {}", id);
+        Ok(id)
+    }
+
+    /// 分页获取任务的变更历史（最新的在前）
+    pub fn history(storage: &impl Storage, input: TodoHistoryInput) -> AppResult<TodoHistoryPage> {
+        let page = input.page.unwrap_or(1).max(1);
+        let page_size = input.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+        if !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+            return Err(AppError::Validation(format!("每页条数必须在 1-{} 之间", MAX_PAGE_SIZE)));
+        }
+
+        tracing::debug!(
+            "AuditService::history - todo_id: {}, page: {}, page_size: {}",
+            input.todo_id, page, page_size
+        );
+        let mut conn = storage.connection()?;
+        let total = todo_audit::table
+            .filter(todo_audit::todo_id.eq(&input.todo_id))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let items = todo_audit::table
+            .filter(todo_audit::todo_id.eq(&input.todo_id))
+            .order(todo_audit::id.desc())
+            .offset((page - 1) * page_size)
+            .limit(page_size)
+            .select(AuditEntry::as_select())
+            .load(&mut conn)?;
+
+        Ok(TodoHistoryPage { items, total, page, page_size })
+    }
+
+    /// 按时间范围导出审计日志为 CSV（按写入顺序）
+    pub fn export_csv(storage: &impl Storage, input: AuditExportInput) -> AppResult<String> {
+        let mut conn = storage.connection()?;
+        let mut query = todo_audit::table.into_boxed();
+        if let Some(since) = input.since {
+            query = query.filter(todo_audit::created_at.ge(since));
+        }
+        if let Some(until) = input.until {
+            query = query.filter(todo_audit::created_at.le(until));
+        }
+        if let Some(todo_id) = input.todo_id {
+            query = query.filter(todo_audit::todo_id.eq(todo_id));
+        }
+        let entries = query
+            .order(todo_audit::id.asc())
+            .select(AuditEntry::as_select())
+            .load(&mut conn)?;
+
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for entry in &entries {
+            let fields = [
+                entry.id.to_string(),
+                csv_field(&entry.todo_id),
+                csv_field(&entry.operation),
+                csv_field(&entry.source),
+                csv_field(&entry.device_id),
+                csv_field(&entry.actor),
+                csv_field(&entry.created_at),
+                csv_field(entry.before_state.as_deref().unwrap_or("")),
+                csv_field(&entry.after_state),
+            ];
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        tracing::info!("Exported {} audit entries", entries.len());
+        Ok(csv)
+    }
+}
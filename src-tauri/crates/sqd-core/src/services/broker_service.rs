@@ -1,4 +1,5 @@
-use crate::db::{get_connection, DbPool, todos};
+use crate::db::todos;
+use crate::storage::Storage;
 use crate::utils::AppResult;
 use diesel::prelude::*;
 
@@ -7,9 +8,9 @@ pub struct BrokerService;
 
 impl BrokerService {
     /// 获取所有券商（去重）
-    pub fn get_pool(pool: &DbPool) -> AppResult<Vec<String>> {
+    pub fn get_pool(storage: &impl Storage) -> AppResult<Vec<String>> {
         tracing::info!("BrokerService::get_pool");
-        let mut conn = get_connection(pool)?;
+        let mut conn = storage.connection()?;
 
         let brokers = todos::table
             .select(todos::broker)
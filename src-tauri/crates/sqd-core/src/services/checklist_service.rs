@@ -0,0 +1,206 @@
+use crate::db::{todo_items, todos, Todo, TodoItem};
+use crate::dto::{
+    AddTodoItemInput, Checklist, DeleteTodoItemInput, ReorderTodoItemsInput, TodosChanged,
+    UpdateTodoItemInput,
+};
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult};
+use chrono::{SecondsFormat, Utc};
+use diesel::prelude::*;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// 单个任务最多的检查项数
+const MAX_ITEMS_PER_TODO: i64 = 200;
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn validate_content(content: &str) -> AppResult<String> {
+    let trimmed = content.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > 500 {
+        return Err(AppError::Validation("检查项内容长度必须在 1-500 字符之间".to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// 未删除的父任务，不存在时返回校验错误
+fn parent(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Todo> {
+    todos::table
+        .find(todo_id)
+        .filter(todos::deleted_at.is_null())
+        .first::<Todo>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::Validation("任务不存在".to_string()))
+}
+
+fn find_item(conn: &mut SqliteConnection, item_id: &str) -> AppResult<TodoItem> {
+    todo_items::table
+        .find(item_id)
+        .first::<TodoItem>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::Validation("检查项不存在".to_string()))
+}
+
+fn load_items(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Vec<TodoItem>> {
+    let items = todo_items::table
+        .filter(todo_items::todo_id.eq(todo_id))
+        .order(todo_items::position.asc())
+        .load::<TodoItem>(conn)?;
+    Ok(items)
+}
+
+/// 检查项服务（检查项只保存在本地，不参与同步）
+pub struct ChecklistService;
+
+impl ChecklistService {
+    /// 重新统计父任务的检查项进度，返回任务与检查项
+    ///
+    /// 进度是本地派生数据，不修改 `updated_at` 与 `version`，以免触发同步或编辑冲突。
+    fn refresh(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Checklist> {
+        let items = load_items(conn, todo_id)?;
+        let done = items.iter().filter(|item| item.done).count() as i32;
+        diesel::update(todos::table.find(todo_id))
+            .set((
+                todos::items_total.eq(items.len() as i32),
+                todos::items_done.eq(done),
+            ))
+            .execute(conn)?;
+        let todo = todos::table.find(todo_id).first::<Todo>(conn)?;
+        Ok(Checklist { todo, items })
+    }
+
+    /// 在事务内执行检查项写操作并刷新进度，提交后通知父任务变化
+    fn mutate<F>(storage: &impl Storage, f: F) -> AppResult<Checklist>
+    where
+        F: FnOnce(&mut SqliteConnection, &str) -> AppResult<String>,
+    {
+        let mut conn = storage.connection()?;
+        let now = now();
+        let checklist = conn.transaction::<_, AppError, _>(|conn| {
+            let todo_id = f(conn, &now)?;
+            Self::refresh(conn, &todo_id)
+        })?;
+        storage.todos_changed(&TodosChanged::updated(checklist.todo.clone()));
+        Ok(checklist)
+    }
+
+    /// 获取任务的检查项
+    pub fn list(storage: &impl Storage, todo_id: &str) -> AppResult<Checklist> {
+        let mut conn = storage.connection()?;
+        Self::list_with_conn(&mut conn, todo_id)
+    }
+
+    /// 同 `list`，使用调用方已持有的连接
+    pub(crate) fn list_with_conn(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Checklist> {
+        let todo = parent(conn, todo_id)?;
+        let items = load_items(conn, todo_id)?;
+        Ok(Checklist { todo, items })
+    }
+
+    /// 添加检查项到末尾
+    pub fn add(storage: &impl Storage, input: AddTodoItemInput) -> AppResult<Checklist> {
+        tracing::debug!("ChecklistService::add - todo_id: {}", input.todo_id);
+        let content = validate_content(&input.content)?;
+        Self::mutate(storage, |conn, now| {
+            parent(conn, &input.todo_id)?;
+            let count = todo_items::table
+                .filter(todo_items::todo_id.eq(&input.todo_id))
+                .count()
+                .get_result::<i64>(conn)?;
+            if count >= MAX_ITEMS_PER_TODO {
+                return Err(AppError::Validation(format!(
+                    "每个任务最多 {} 个检查项",
+                    MAX_ITEMS_PER_TODO
+                )));
+            }
+            let position = todo_items::table
+                .filter(todo_items::todo_id.eq(&input.todo_id))
+                .select(diesel::dsl::max(todo_items::position))
+                .first::<Option<i32>>(conn)?
+                .map_or(0, |max| max + 1);
+
+            diesel::insert_into(todo_items::table)
+                .values(&TodoItem {
+                    id: Uuid::new_v4().to_string(),
+                    todo_id: input.todo_id.clone(),
+                    position,
+                    content,
+                    done: false,
+                    created_at: now.to_string(),
+                    updated_at: now.to_string(),
+                })
+                .execute(conn)?;
+            Ok(input.todo_id)
+        })
+    }
+
+    /// 修改检查项内容或勾选状态
+    pub fn update(storage: &impl Storage, input: UpdateTodoItemInput) -> AppResult<Checklist> {
+        tracing::debug!("ChecklistService::update - item_id: {}", input.item_id);
+        let content = input.content.as_deref().map(validate_content).transpose()?;
+        Self::mutate(storage, |conn, now| {
+            let mut item = find_item(conn, &input.item_id)?;
+            parent(conn, &item.todo_id)?;
+            if let Some(content) = content {
+                item.content = content;
+            }
+            if let Some(done) = input.done {
+                item.done = done;
+            }
+            diesel::update(todo_items::table.find(&item.id))
+                .set((
+                    todo_items::content.eq(&item.content),
+                    todo_items::done.eq(item.done),
+                    todo_items::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+            Ok(item.todo_id)
+        })
+    }
+
+    /// 删除检查项，之后的检查项顺序前移
+    pub fn delete(storage: &impl Storage, input: DeleteTodoItemInput) -> AppResult<Checklist> {
+        tracing::debug!("ChecklistService::delete - item_id: {}", input.item_id);
+        Self::mutate(storage, |conn, _| {
+            let item = find_item(conn, &input.item_id)?;
+            parent(conn, &item.todo_id)?;
+            diesel::delete(todo_items::table.find(&item.id)).execute(conn)?;
+            diesel::update(
+                todo_items::table
+                    .filter(todo_items::todo_id.eq(&item.todo_id))
+                    .filter(todo_items::position.gt(item.position)),
+            )
+            .set(todo_items::position.eq(todo_items::position - 1))
+            .execute(conn)?;
+            Ok(item.todo_id)
+        })
+    }
+
+    /// 按给定顺序重新排列任务的全部检查项
+    pub fn reorder(storage: &impl Storage, input: ReorderTodoItemsInput) -> AppResult<Checklist> {
+        tracing::debug!("ChecklistService::reorder - todo_id: {}", input.todo_id);
+        Self::mutate(storage, |conn, now| {
+            parent(conn, &input.todo_id)?;
+            let existing = load_items(conn, &input.todo_id)?
+                .into_iter()
+                .map(|item| item.id)
+                .collect::<HashSet<_>>();
+            let requested = input.item_ids.iter().cloned().collect::<HashSet<_>>();
+            if requested.len() != input.item_ids.len() || requested != existing {
+                return Err(AppError::Validation("检查项列表已变化，请刷新后重试".to_string()));
+            }
+
+            for (position, id) in input.item_ids.iter().enumerate() {
+                diesel::update(todo_items::table.find(id))
+                    .set((
+                        todo_items::position.eq(position as i32),
+                        todo_items::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(input.todo_id)
+        })
+    }
+}
@@ -0,0 +1,457 @@
+use crate::db::{custom_fields, todos, CustomField};
+use crate::dto::{
+    CreateCustomFieldInput, CustomFieldView, DeleteCustomFieldInput, FieldFilter, FieldType,
+    ReorderCustomFieldsInput, UpdateCustomFieldInput,
+};
+use crate::storage::Storage;
+use crate::utils::{escape_like_pattern, AppError, AppResult};
+use chrono::{NaiveDate, SecondsFormat, Utc};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Double, Text};
+use diesel::sqlite::Sqlite;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// 每个工作区最多的字段数
+const MAX_FIELDS: i64 = 50;
+/// select 字段最多的选项数
+const MAX_OPTIONS: usize = 50;
+/// 文本字段值的最大长度
+const MAX_TEXT_LEN: usize = 500;
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 标识：小写字母开头，只含小写字母、数字、下划线，最多 40 字符
+fn validate_key(key: &str) -> AppResult<String> {
+    let key = key.trim();
+    let valid = !key.is_empty()
+        && key.len() <= 40
+        && key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(AppError::Validation(
+            "字段标识只能包含小写字母、数字和下划线，以字母开头，最多 40 字符".to_string(),
+        ));
+    }
+    Ok(key.to_string())
+}
+
+fn validate_name(name: &str) -> AppResult<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > 50 {
+        return Err(AppError::Validation("字段名称长度必须在 1-50 字符之间".to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// select 字段至少一个选项，其他类型不能有选项；返回保存用的 JSON
+fn validate_options(field_type: FieldType, options: &[String]) -> AppResult<Option<String>> {
+    if field_type != FieldType::Select {
+        if !options.is_empty() {
+            return Err(AppError::Validation("只有选择字段可以设置选项".to_string()));
+        }
+        return Ok(None);
+    }
+
+    let options = options
+        .iter()
+        .map(|option| option.trim().to_string())
+        .filter(|option| !option.is_empty())
+        .collect::<Vec<_>>();
+    if options.is_empty() || options.len() > MAX_OPTIONS {
+        return Err(AppError::Validation(format!("选择字段的选项数必须在 1-{} 之间", MAX_OPTIONS)));
+    }
+    if options.iter().any(|option| option.chars().count() > 100) {
+        return Err(AppError::Validation("选项长度不能超过 100 字符".to_string()));
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = options.iter().find(|option| !seen.insert(option.as_str())) {
+        return Err(AppError::Validation(format!("选项「{}」重复", duplicate)));
+    }
+    let json = serde_json::to_string(&options)
+        .map_err(|e| AppError::Validation(format!("无法保存字段选项: {}", e)))?;
+    Ok(Some(json))
+}
+
+/// 按字段类型校验一个值，返回规范化后的值；`None` 表示清除
+fn validate_value(field: &CustomFieldView, value: &Value) -> AppResult<Option<Value>> {
+    let invalid = |expected: &str| {
+        AppError::Validation(format!("字段「{}」的值必须是{}", field.name, expected))
+    };
+
+    if value.is_null() {
+        return Ok(None);
+    }
+    if field.field_type == FieldType::Number {
+        return match value {
+            Value::Number(_) => Ok(Some(value.clone())),
+            _ => Err(invalid("数字")),
+        };
+    }
+
+    let text = value.as_str().ok_or_else(|| invalid("文本"))?.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match field.field_type {
+        FieldType::Text if text.chars().count() > MAX_TEXT_LEN => Err(AppError::Validation(
+            format!("字段「{}」不能超过 {} 字符", field.name, MAX_TEXT_LEN),
+        )),
+        FieldType::Date if NaiveDate::parse_from_str(text, "%Y-%m-%d").is_err() => {
+            Err(invalid(" YYYY-MM-DD 格式的日期"))
+        }
+        FieldType::Select if !field.options.iter().any(|option| option == text) => Err(
+            AppError::Validation(format!("「{}」不是字段「{}」的选项", text, field.name)),
+        ),
+        _ => Ok(Some(Value::String(text.to_string()))),
+    }
+}
+
+fn find_field(conn: &mut SqliteConnection, field_id: &str) -> AppResult<CustomField> {
+    custom_fields::table
+        .find(field_id)
+        .first::<CustomField>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::Validation("字段不存在".to_string()))
+}
+
+fn load_views(conn: &mut SqliteConnection) -> AppResult<Vec<CustomFieldView>> {
+    custom_fields::table
+        .order(custom_fields::position.asc())
+        .load::<CustomField>(conn)?
+        .into_iter()
+        .map(CustomFieldView::try_from)
+        .collect()
+}
+
+/// 自定义字段服务
+///
+/// 字段定义保存在各工作区的数据库中，不参与同步；任务中的字段值以字段标识为键，
+/// 随任务一起同步。删除定义不会清除任务中的值，重新创建同一标识的字段后值会再次显示。
+pub struct CustomFieldService;
+
+impl CustomFieldService {
+    /// 校验字段值修改并合并到任务现有的值（JSON 文本），返回合并后的 JSON
+    ///
+    /// 没有定义的字段标识返回校验错误；现有值中没有定义的字段（如来自其他设备）原样保留。
+    pub(crate) fn apply_values(
+        conn: &mut SqliteConnection,
+        current: &str,
+        changes: &Map<String, Value>,
+    ) -> AppResult<String> {
+        let mut values = serde_json::from_str::<Map<String, Value>>(current).unwrap_or_default();
+        if changes.is_empty() {
+            return Ok(Value::Object(values).to_string());
+        }
+
+        let fields = load_views(conn)?
+            .into_iter()
+            .map(|field| (field.key.clone(), field))
+            .collect::<HashMap<_, _>>();
+        for (key, value) in changes {
+            let field = fields
+                .get(key)
+                .ok_or_else(|| AppError::Validation(format!("未知的自定义字段: {}", key)))?;
+            match validate_value(field, value)? {
+                Some(value) => values.insert(key.clone(), value),
+                None => values.remove(key),
+            };
+        }
+        Ok(Value::Object(values).to_string())
+    }
+
+    /// 把字段过滤条件加到任务查询上（SQLite `json_extract`，值均以参数绑定）
+    pub(crate) fn filter_todos<'a>(
+        mut query: todos::BoxedQuery<'a, Sqlite>,
+        filter: &FieldFilter,
+    ) -> AppResult<todos::BoxedQuery<'a, Sqlite>> {
+        let path = format!("$.{}", validate_key(&filter.key)?);
+        let value = || sql::<Bool>("json_extract(custom_fields, ").bind::<Text, _>(path.clone());
+        let value_type = || sql::<Bool>("json_type(custom_fields, ").bind::<Text, _>(path.clone());
+
+        if let Some(text) = filter.contains.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            let pattern = format!("%{}%", escape_like_pattern(text));
+            query = query.filter(value().sql(") LIKE ").bind::<Text, _>(pattern).sql(" ESCAPE '\\'"));
+        }
+        if let Some(option) = filter.equals.as_deref().filter(|o| !o.is_empty()) {
+            query = query.filter(value().sql(") = ").bind::<Text, _>(option.to_string()));
+        }
+        if filter.min.is_some() || filter.max.is_some() {
+            query = query.filter(value_type().sql(") IN ('integer', 'real')"));
+        }
+        if let Some(min) = filter.min {
+            query = query.filter(value().sql(") >= ").bind::<Double, _>(min));
+        }
+        if let Some(max) = filter.max {
+            query = query.filter(value().sql(") <= ").bind::<Double, _>(max));
+        }
+        // YYYY-MM-DD 可直接按字符串比较
+        if filter.from.is_some() || filter.to.is_some() {
+            query = query.filter(value_type().sql(") = 'text'"));
+        }
+        if let Some(from) = &filter.from {
+            query = query.filter(value().sql(") >= ").bind::<Text, _>(from.clone()));
+        }
+        if let Some(to) = &filter.to {
+            query = query.filter(value().sql(") <= ").bind::<Text, _>(to.clone()));
+        }
+        Ok(query)
+    }
+
+    /// 获取所有字段定义（按顺序）
+    pub fn list(storage: &impl Storage) -> AppResult<Vec<CustomFieldView>> {
+        let mut conn = storage.connection()?;
+        load_views(&mut conn)
+    }
+
+    /// 创建字段，排在最后
+    pub fn create(storage: &impl Storage, input: CreateCustomFieldInput) -> AppResult<CustomFieldView> {
+        let key = validate_key(&input.key)?;
+        let name = validate_name(&input.name)?;
+        let options = validate_options(input.field_type, &input.options)?;
+
+        let mut conn = storage.connection()?;
+        let field = conn.transaction::<_, AppError, _>(|conn| {
+            if custom_fields::table.count().get_result::<i64>(conn)? >= MAX_FIELDS {
+                return Err(AppError::Validation(format!("最多 {} 个自定义字段", MAX_FIELDS)));
+            }
+            let taken = custom_fields::table
+                .filter(custom_fields::key.eq(&key))
+                .count()
+                .get_result::<i64>(conn)?;
+            if taken > 0 {
+                return Err(AppError::Validation(format!("字段标识「{}」已存在", key)));
+            }
+            let position = custom_fields::table
+                .select(diesel::dsl::max(custom_fields::position))
+                .first::<Option<i32>>(conn)?
+                .map_or(0, |max| max + 1);
+
+            let now = now();
+            let field = CustomField {
+                id: Uuid::new_v4().to_string(),
+                key,
+                name,
+                field_type: input.field_type.as_str().to_string(),
+                options,
+                position,
+                created_at: now.clone(),
+                updated_at: now,
+            };
+            diesel::insert_into(custom_fields::table).values(&field).execute(conn)?;
+            Ok(field)
+        })?;
+
+        tracing::info!("Created custom field {} ({})", field.key, field.field_type);
+        CustomFieldView::try_from(field)
+    }
+
+    /// 修改字段名称或选项
+    ///
+    /// 删除选项不会修改已使用该选项的任务，这些任务下次修改该字段时需要重新选择。
+    pub fn update(storage: &impl Storage, input: UpdateCustomFieldInput) -> AppResult<CustomFieldView> {
+        let name = input.name.as_deref().map(validate_name).transpose()?;
+
+        let mut conn = storage.connection()?;
+        let field = conn.transaction::<_, AppError, _>(|conn| {
+            let mut field = find_field(conn, &input.field_id)?;
+            if let Some(name) = name {
+                field.name = name;
+            }
+            if let Some(ref options) = input.options {
+                let field_type = FieldType::parse_stored(&field.field_type)?;
+                field.options = validate_options(field_type, options)?;
+            }
+            field.updated_at = now();
+            diesel::update(custom_fields::table.find(&field.id))
+                .set((
+                    custom_fields::name.eq(&field.name),
+                    custom_fields::options.eq(&field.options),
+                    custom_fields::updated_at.eq(&field.updated_at),
+                ))
+                .execute(conn)?;
+            Ok(field)
+        })?;
+
+        tracing::info!("Updated custom field {}", field.key);
+        CustomFieldView::try_from(field)
+    }
+
+    /// 删除字段定义（任务中的值保留）
+    pub fn delete(storage: &impl Storage, input: DeleteCustomFieldInput) -> AppResult<()> {
+        let mut conn = storage.connection()?;
+        let affected = diesel::delete(custom_fields::table.find(&input.field_id)).execute(&mut conn)?;
+        if affected == 0 {
+            return Err(AppError::Validation("字段不存在".to_string()));
+        }
+        tracing::info!("Deleted custom field {}", input.field_id);
+        Ok(())
+    }
+
+    /// 按给定顺序重排全部字段
+    pub fn reorder(storage: &impl Storage, input: ReorderCustomFieldsInput) -> AppResult<Vec<CustomFieldView>> {
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let existing = custom_fields::table
+                .select(custom_fields::id)
+                .load::<String>(conn)?
+                .into_iter()
+                .collect::<HashSet<_>>();
+            let requested = input.field_ids.iter().cloned().collect::<HashSet<_>>();
+            if requested.len() != input.field_ids.len() || requested != existing {
+                return Err(AppError::Validation("字段列表已变化，请刷新后重试".to_string()));
+            }
+
+            let now = now();
+            for (position, id) in input.field_ids.iter().enumerate() {
+                diesel::update(custom_fields::table.find(id))
+                    .set((
+                        custom_fields::position.eq(position as i32),
+                        custom_fields::updated_at.eq(&now),
+                    ))
+                    .execute(conn)?;
+            }
+            load_views(conn)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::{CreateTodoInput, ListTodosInput};
+    use crate::services::TodoService;
+    use crate::test_support::memory_pool;
+    use serde_json::json;
+
+    fn view(field_type: FieldType, options: &[&str]) -> CustomFieldView {
+        CustomFieldView {
+            id: "field".to_string(),
+            key: "field".to_string(),
+            name: "字段".to_string(),
+            field_type,
+            options: options.iter().map(|o| o.to_string()).collect(),
+            position: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn values_are_checked_against_field_type() {
+        let number = view(FieldType::Number, &[]);
+        assert_eq!(validate_value(&number, &json!(12.5)).unwrap(), Some(json!(12.5)));
+        assert!(validate_value(&number, &json!("12")).is_err());
+
+        let date = view(FieldType::Date, &[]);
+        assert_eq!(validate_value(&date, &json!(" 2028-02-29 ")).unwrap(), Some(json!("2028-02-29")));
+        assert!(validate_value(&date, &json!("2027-02-29")).is_err());
+        assert!(validate_value(&date, &json!(20270101)).is_err());
+
+        let select = view(FieldType::Select, &["高", "低"]);
+        assert_eq!(validate_value(&select, &json!("高")).unwrap(), Some(json!("高")));
+        assert!(validate_value(&select, &json!("中")).is_err());
+
+        let text = view(FieldType::Text, &[]);
+        assert!(validate_value(&text, &json!("x".repeat(MAX_TEXT_LEN + 1))).is_err());
+        // null 与空白文本表示清除
+        assert_eq!(validate_value(&text, &Value::Null).unwrap(), None);
+        assert_eq!(validate_value(&text, &json!("  ")).unwrap(), None);
+    }
+
+    #[test]
+    fn options_only_allowed_on_select_fields() {
+        assert!(validate_options(FieldType::Text, &["a".to_string()]).is_err());
+        assert!(validate_options(FieldType::Select, &[]).is_err());
+        assert!(validate_options(FieldType::Select, &["a".to_string(), " a ".to_string()]).is_err());
+        assert_eq!(
+            validate_options(FieldType::Select, &["a".to_string(), "b".to_string()]).unwrap(),
+            Some(r#"["a","b"]"#.to_string())
+        );
+    }
+
+    #[test]
+    fn apply_values_rejects_unknown_keys_and_keeps_foreign_values() {
+        let pool = memory_pool();
+        CustomFieldService::create(
+            &pool,
+            CreateCustomFieldInput {
+                key: "amount".to_string(),
+                name: "金额".to_string(),
+                field_type: FieldType::Number,
+                options: Vec::new(),
+            },
+        )
+        .unwrap();
+        let mut conn = pool.connection().unwrap();
+
+        let unknown = json!({ "missing": 1 }).as_object().unwrap().clone();
+        assert!(CustomFieldService::apply_values(&mut conn, "{}", &unknown).is_err());
+
+        let changes = json!({ "amount": 3 }).as_object().unwrap().clone();
+        let merged = CustomFieldService::apply_values(&mut conn, r#"{"other":"x"}"#, &changes).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&merged).unwrap(), json!({ "amount": 3, "other": "x" }));
+    }
+
+    #[test]
+    fn filter_todos_matches_values_by_field_type() {
+        let pool = memory_pool();
+        for (key, field_type) in [("note", FieldType::Text), ("amount", FieldType::Number), ("due", FieldType::Date)] {
+            let input = CreateCustomFieldInput {
+                key: key.to_string(),
+                name: key.to_string(),
+                field_type,
+                options: Vec::new(),
+            };
+            CustomFieldService::create(&pool, input).unwrap();
+        }
+        for (title, values) in [
+            ("甲", json!({ "note": "Alpha 100%", "amount": 5, "due": "2026-01-10" })),
+            ("乙", json!({ "note": "beta", "amount": 50.5, "due": "2026-03-01" })),
+            ("丙", json!({})),
+        ] {
+            let input = CreateTodoInput {
+                title: title.to_string(),
+                status: "pending".to_string(),
+                broker: "测试券商".to_string(),
+                conclusion: None,
+                custom_fields: values.as_object().cloned(),
+            };
+            TodoService::create(&pool, input).unwrap();
+        }
+
+        let titles = |filter: FieldFilter| {
+            let input = ListTodosInput { tag_ids: Vec::new(), field_filter: Some(filter) };
+            let mut titles = TodoService::list(&pool, input)
+                .unwrap()
+                .into_iter()
+                .map(|todo| todo.title)
+                .collect::<Vec<_>>();
+            titles.sort();
+            titles
+        };
+        let filter = |key: &str| FieldFilter { key: key.to_string(), ..Default::default() };
+
+        assert_eq!(titles(FieldFilter { contains: Some("ALPHA".to_string()), ..filter("note") }), vec!["甲"]);
+        // LIKE 通配符按字面匹配
+        assert_eq!(titles(FieldFilter { contains: Some("0%".to_string()), ..filter("note") }), vec!["甲"]);
+        assert!(titles(FieldFilter { contains: Some("_".to_string()), ..filter("note") }).is_empty());
+        assert_eq!(titles(FieldFilter { min: Some(10.0), ..filter("amount") }), vec!["乙"]);
+        assert_eq!(titles(FieldFilter { max: Some(10.0), ..filter("amount") }), vec!["甲"]);
+        assert_eq!(
+            titles(FieldFilter { from: Some("2026-01-01".to_string()), to: Some("2026-01-31".to_string()), ..filter("due") }),
+            vec!["甲"]
+        );
+        // 没有条件时不过滤
+        assert_eq!(titles(filter("amount")), vec!["丙", "乙", "甲"]);
+        assert!(TodoService::list(
+            &pool,
+            ListTodosInput { tag_ids: Vec::new(), field_filter: Some(filter("bad key')")) }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::db::{todos, undo_journal, JournalEntry, NewJournalEntry, Todo};
+use crate::dto::TodosChanged;
+use crate::services::audit_service::{AuditWriter, OP_REDO, OP_UNDO};
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult};
+use chrono::{SecondsFormat, Utc};
+use diesel::prelude::*;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// 每个会话最多保留的撤销记录数
+const UNDO_JOURNAL_LIMIT: i64 = 100;
+
+pub const OP_CREATE: &str = "create";
+pub const OP_UPDATE: &str = "update";
+pub const OP_DELETE: &str = "delete";
+
+static SESSION_ID: OnceLock<String> = OnceLock::new();
+
+/// 当前会话（进程运行期）标识，只能撤销本次运行中的操作
+pub fn session_id() -> &'static str {
+    SESSION_ID.get_or_init(|| Uuid::new_v4().to_string())
+}
+
+fn to_json(todo: &Todo) -> AppResult<String> {
+    serde_json::to_string(todo)
+        .map_err(|e| AppError::Validation(format!("无法序列化撤销记录: {}", e)))
+}
+
+fn from_json(value: &str) -> AppResult<Todo> {
+    serde_json::from_str(value)
+        .map_err(|e| AppError::Validation(format!("撤销记录已损坏: {}", e)))
+}
+
+/// 一次撤销/重做的结果
+enum StepOutcome {
+    /// 没有可撤销（重做）的记录
+    Empty,
+    Applied(Todo),
+    /// 任务已被其他写入修改，记录已丢弃
+    Stale(Todo),
+}
+
+/// 撤销/重做日志服务
+pub struct JournalService;
+
+impl JournalService {
+    /// 记录一次写操作，需与写操作在同一事务内调用
+    ///
+    /// 新操作会清空重做栈，并只保留最近 `UNDO_JOURNAL_LIMIT` 条记录。
+    pub(crate) fn record(
+        conn: &mut SqliteConnection,
+        operation: &str,
+        before: Option<&Todo>,
+        after: &Todo,
+    ) -> AppResult<()> {
+        let session = session_id();
+
+        // 已撤销的记录不能再重做；其他会话遗留的记录一并清理
+        diesel::delete(
+            undo_journal::table.filter(
+                undo_journal::session_id
+                    .ne(session)
+                    .or(undo_journal::undone.eq(true)),
+            ),
+        )
+        .execute(conn)?;
+
+        let entry = NewJournalEntry {
+            session_id: session.to_string(),
+            todo_id: after.id.clone(),
+            operation: operation.to_string(),
+            before_state: before.map(to_json).transpose()?,
+            after_state: to_json(after)?,
+            expected_version: after.version,
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        };
+        diesel::insert_into(undo_journal::table)
+            .values(&entry)
+            .execute(conn)?;
+
+        let oldest_kept = undo_journal::table
+            .select(undo_journal::id)
+            .order(undo_journal::id.desc())
+            .offset(UNDO_JOURNAL_LIMIT - 1)
+            .first::<i32>(conn)
+            .optional()?;
+        if let Some(oldest_kept) = oldest_kept {
+            diesel::delete(undo_journal::table.filter(undo_journal::id.lt(oldest_kept)))
+                .execute(conn)?;
+        }
+        Ok(())
+    }
+
+    /// 撤销本会话最近一次操作，返回受影响的任务；没有可撤销的操作时返回 `None`
+    pub fn undo(storage: &impl Storage) -> AppResult<Option<Todo>> {
+        Self::step(storage, true)
+    }
+
+    /// 重做最近一次撤销的操作
+    pub fn redo(storage: &impl Storage) -> AppResult<Option<Todo>> {
+        Self::step(storage, false)
+    }
+
+    fn next_entry(conn: &mut SqliteConnection, undo: bool) -> AppResult<Option<JournalEntry>> {
+        let query = undo_journal::table
+            .filter(undo_journal::session_id.eq(session_id()))
+            .filter(undo_journal::undone.eq(!undo))
+            .select(JournalEntry::as_select());
+        // 撤销取最新的记录，重做取最早被撤销的记录
+        let entry = if undo {
+            query.order(undo_journal::id.desc()).first(conn).optional()?
+        } else {
+            query.order(undo_journal::id.asc()).first(conn).optional()?
+        };
+        Ok(entry)
+    }
+
+    fn step(storage: &impl Storage, undo: bool) -> AppResult<Option<Todo>> {
+        let action = if undo { "undo" } else { "redo" };
+        tracing::debug!("JournalService::{}", action);
+        let mut conn = storage.connection()?;
+
+        let outcome = conn.transaction::<_, AppError, _>(|conn| loop {
+            let Some(entry) = Self::next_entry(conn, undo)? else {
+                return Ok(StepOutcome::Empty);
+            };
+
+            let current = todos::table
+                .find(&entry.todo_id)
+                .first::<Todo>(conn)
+                .optional()?;
+            let Some(current) = current else {
+                // 任务已被永久删除（清空回收站），跳过该记录
+                diesel::delete(undo_journal::table.find(entry.id)).execute(conn)?;
+                continue;
+            };
+            if current.version != entry.expected_version {
+                diesel::delete(undo_journal::table.find(entry.id)).execute(conn)?;
+                return Ok(StepOutcome::Stale(current));
+            }
+
+            // 撤销恢复操作前的行（create 没有前态，改为移入回收站），重做恢复操作后的行
+            let target = if undo {
+                entry.before_state.as_deref().map(from_json).transpose()?
+            } else {
+                Some(from_json(&entry.after_state)?)
+            };
+            let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+            let row = || todos::table.find(&entry.todo_id);
+            match &target {
+                Some(state) => diesel::update(row())
+                    .set((
+                        todos::title.eq(&state.title),
+                        todos::status.eq(&state.status),
+                        todos::broker.eq(&state.broker),
+                        todos::conclusion.eq(&state.conclusion),
+                        todos::deleted_at.eq(state.deleted_at.as_ref().map(|_| &now)),
+                        todos::restored_at.eq(&state.restored_at),
+                        todos::restored_by.eq(&state.restored_by),
+                        todos::custom_fields.eq(&state.custom_fields),
+                        todos::updated_at.eq(&now),
+                        todos::version.eq(todos::version + 1),
+                    ))
+                    .execute(conn)?,
+                None => diesel::update(row())
+                    .set((
+                        todos::deleted_at.eq(Some(&now)),
+                        todos::updated_at.eq(&now),
+                        todos::version.eq(todos::version + 1),
+                    ))
+                    .execute(conn)?,
+            };
+
+            let todo = row().first::<Todo>(conn)?;
+            diesel::update(undo_journal::table.find(entry.id))
+                .set((
+                    undo_journal::undone.eq(undo),
+                    undo_journal::expected_version.eq(todo.version),
+                ))
+                .execute(conn)?;
+            let operation = if undo { OP_UNDO } else { OP_REDO };
+            AuditWriter::new(conn, storage.change_source())?.record(conn, operation, Some(&current), &todo)?;
+
+            tracing::info!("{} {} on todo {}", action, entry.operation, todo.id);
+            return Ok(StepOutcome::Applied(todo));
+        })?;
+
+        match outcome {
+            StepOutcome::Empty => Ok(None),
+            StepOutcome::Stale(current) => {
+                tracing::info!("Cannot {} todo {}: modified since", action, current.id);
+                Err(AppError::Conflict(Box::new(current)))
+            }
+            StepOutcome::Applied(todo) => {
+                let changes = if todo.deleted_at.is_some() {
+                    TodosChanged::deleted(todo.id.clone())
+                } else {
+                    TodosChanged::updated(todo.clone())
+                };
+                storage.todos_changed(&changes);
+                Ok(Some(todo))
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::db::{todo_links, todos, NewTodoLink, Todo, TodoLink};
+use crate::dto::{CreateLinkInput, DeleteLinkInput, LinkKind, LinkedTodo, TodoDetail};
+use crate::services::ChecklistService;
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult};
+use chrono::{SecondsFormat, Utc};
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 未删除的任务，不存在时返回校验错误
+fn live_todo(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Todo> {
+    todos::table
+        .find(todo_id)
+        .filter(todos::deleted_at.is_null())
+        .first::<Todo>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::Validation("任务不存在".to_string()))
+}
+
+/// 沿 `kind` 类型的关联（source → target）能否从 `from` 走到 `to`
+fn reaches(conn: &mut SqliteConnection, from: &str, to: &str, kind: LinkKind) -> AppResult<bool> {
+    let edges = todo_links::table
+        .filter(todo_links::kind.eq(kind.as_str()))
+        .select((todo_links::source_id, todo_links::target_id))
+        .load::<(String, String)>(conn)?;
+    let mut next: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (source, target) in &edges {
+        next.entry(source.as_str()).or_default().push(target.as_str());
+    }
+
+    let mut seen = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            return Ok(true);
+        }
+        for &target in next.get(current).into_iter().flatten() {
+            if seen.insert(target) {
+                queue.push_back(target);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// 任务关联服务（关联只保存在本地，不参与同步）
+pub struct LinkService;
+
+impl LinkService {
+    /// 阻塞该任务且尚未完成的任务
+    pub(crate) fn open_blockers(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<Vec<Todo>> {
+        let blocker_ids = todo_links::table
+            .filter(todo_links::target_id.eq(todo_id))
+            .filter(todo_links::kind.eq(LinkKind::Blocks.as_str()))
+            .select(todo_links::source_id)
+            .load::<String>(conn)?;
+        if blocker_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let blockers = todos::table
+            .filter(todos::id.eq_any(&blocker_ids))
+            .filter(todos::deleted_at.is_null())
+            .filter(todos::status.ne("completed"))
+            .load::<Todo>(conn)?;
+        Ok(blockers)
+    }
+
+    /// 创建关联，返回 `todo_id` 的详情
+    ///
+    /// 阻塞与重复关联不能成环；相关关联不区分方向，同一对任务只保存一条。
+    pub fn create(storage: &impl Storage, input: CreateLinkInput) -> AppResult<TodoDetail> {
+        tracing::debug!(
+            "LinkService::create - {} {} {}",
+            input.todo_id, input.kind.as_str(), input.other_id
+        );
+        if input.todo_id == input.other_id {
+            return Err(AppError::Validation("不能关联任务自身".to_string()));
+        }
+        let (kind, source, target) = input.kind.normalize(&input.todo_id, &input.other_id);
+
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            live_todo(conn, source)?;
+            live_todo(conn, target)?;
+
+            let mut existing = todo_links::table
+                .filter(todo_links::kind.eq(kind.as_str()))
+                .into_boxed();
+            existing = if kind == LinkKind::RelatesTo {
+                existing.filter(
+                    todo_links::source_id
+                        .eq(source)
+                        .and(todo_links::target_id.eq(target))
+                        .or(todo_links::source_id.eq(target).and(todo_links::target_id.eq(source))),
+                )
+            } else {
+                existing
+                    .filter(todo_links::source_id.eq(source))
+                    .filter(todo_links::target_id.eq(target))
+            };
+            if existing.count().get_result::<i64>(conn)? > 0 {
+                return Err(AppError::Validation("关联已存在".to_string()));
+            }
+
+            if kind != LinkKind::RelatesTo && reaches(conn, target, source, kind)? {
+                let message = match kind {
+                    LinkKind::Blocks => "会形成循环阻塞",
+                    _ => "会形成循环重复",
+                };
+                return Err(AppError::Validation(message.to_string()));
+            }
+
+            diesel::insert_into(todo_links::table)
+                .values(&NewTodoLink {
+                    source_id: source.to_string(),
+                    target_id: target.to_string(),
+                    kind: kind.as_str().to_string(),
+                    created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                })
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        tracing::info!("Linked todo {} {} {}", source, kind.as_str(), target);
+        Self::detail_with_conn(&mut conn, &input.todo_id)
+    }
+
+    /// 删除关联
+    pub fn delete(storage: &impl Storage, input: DeleteLinkInput) -> AppResult<()> {
+        let mut conn = storage.connection()?;
+        let affected = diesel::delete(todo_links::table.find(input.link_id)).execute(&mut conn)?;
+        if affected == 0 {
+            return Err(AppError::Validation("关联不存在".to_string()));
+        }
+        tracing::info!("Deleted todo link {}", input.link_id);
+        Ok(())
+    }
+
+    /// 任务详情：任务、检查项与关联任务（关联类型从该任务看）
+    pub fn detail(storage: &impl Storage, todo_id: &str) -> AppResult<TodoDetail> {
+        let mut conn = storage.connection()?;
+        Self::detail_with_conn(&mut conn, todo_id)
+    }
+
+    /// 同 `detail`，使用调用方已持有的连接
+    pub(crate) fn detail_with_conn(conn: &mut SqliteConnection, todo_id: &str) -> AppResult<TodoDetail> {
+        let checklist = ChecklistService::list_with_conn(conn, todo_id)?;
+
+        let links = todo_links::table
+            .filter(todo_links::source_id.eq(todo_id).or(todo_links::target_id.eq(todo_id)))
+            .order(todo_links::id.asc())
+            .load::<TodoLink>(conn)?;
+        let other_ids = links
+            .iter()
+            .map(|link| if link.source_id == todo_id { &link.target_id } else { &link.source_id })
+            .collect::<Vec<_>>();
+        let others = todos::table
+            .filter(todos::id.eq_any(other_ids))
+            .filter(todos::deleted_at.is_null())
+            .load::<Todo>(conn)?
+            .into_iter()
+            .map(|todo| (todo.id.clone(), todo))
+            .collect::<HashMap<_, _>>();
+
+        let mut linked = Vec::with_capacity(links.len());
+        for link in links {
+            let stored = LinkKind::parse_stored(&link.kind)?;
+            let (kind, other_id) = if link.source_id == todo_id {
+                (stored, &link.target_id)
+            } else {
+                (stored.inverse(), &link.source_id)
+            };
+            // 回收站中的任务不显示
+            if let Some(todo) = others.get(other_id).cloned() {
+                linked.push(LinkedTodo { link_id: link.id, kind, todo });
+            }
+        }
+
+        Ok(TodoDetail {
+            todo: checklist.todo,
+            items: checklist.items,
+            links: linked,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbPool;
+    use crate::dto::UpdateTodoInput;
+    use crate::services::TodoService;
+    use crate::test_support::{create_todo, memory_pool};
+
+    fn link(pool: &DbPool, todo: &Todo, kind: LinkKind, other: &Todo) -> AppResult<TodoDetail> {
+        LinkService::create(
+            pool,
+            CreateLinkInput { todo_id: todo.id.clone(), other_id: other.id.clone(), kind },
+        )
+    }
+
+    fn set_status(pool: &DbPool, todo: &Todo, status: &str, conclusion: Option<&str>) -> AppResult<Todo> {
+        TodoService::update(
+            pool,
+            UpdateTodoInput {
+                todo_id: todo.id.clone(),
+                title: None,
+                status: Some(status.to_string()),
+                broker: None,
+                conclusion: conclusion.map(str::to_string),
+                custom_fields: None,
+                expected_version: None,
+            },
+        )
+    }
+
+    #[test]
+    fn blocks_links_cannot_form_a_cycle() {
+        let pool = memory_pool();
+        let (a, b, c) = (create_todo(&pool, "A"), create_todo(&pool, "B"), create_todo(&pool, "C"));
+        link(&pool, &a, LinkKind::Blocks, &b).unwrap();
+        link(&pool, &b, LinkKind::Blocks, &c).unwrap();
+
+        // C → A 会形成 A → B → C → A
+        assert!(matches!(link(&pool, &c, LinkKind::Blocks, &a), Err(AppError::Validation(_))));
+        // 从另一端声明同样会成环
+        assert!(matches!(link(&pool, &a, LinkKind::BlockedBy, &c), Err(AppError::Validation(_))));
+        // 相关关联不限制方向
+        link(&pool, &c, LinkKind::RelatesTo, &a).unwrap();
+    }
+
+    #[test]
+    fn duplicate_links_cannot_form_a_cycle() {
+        let pool = memory_pool();
+        let (a, b) = (create_todo(&pool, "A"), create_todo(&pool, "B"));
+        link(&pool, &a, LinkKind::DuplicateOf, &b).unwrap();
+        assert!(matches!(link(&pool, &b, LinkKind::DuplicateOf, &a), Err(AppError::Validation(_))));
+        assert!(matches!(link(&pool, &a, LinkKind::RelatesTo, &a), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn open_blocker_prevents_starting() {
+        let pool = memory_pool();
+        let (blocker, blocked) = (create_todo(&pool, "前置"), create_todo(&pool, "后续"));
+        link(&pool, &blocked, LinkKind::BlockedBy, &blocker).unwrap();
+
+        assert!(matches!(set_status(&pool, &blocked, "in_progress", None), Err(AppError::Validation(_))));
+
+        set_status(&pool, &blocker, "completed", Some("已完成")).unwrap();
+        let started = set_status(&pool, &blocked, "in_progress", None).unwrap();
+        assert_eq!(started.status, "in_progress");
+    }
+}
@@ -0,0 +1,23 @@
+pub mod todo_service;
+pub mod broker_service;
+pub mod sync_service;
+pub mod journal_service;
+pub mod audit_service;
+pub mod recurrence_service;
+pub mod template_service;
+pub mod checklist_service;
+pub mod link_service;
+pub mod tag_service;
+pub mod custom_field_service;
+
+pub use todo_service::TodoService;
+pub use broker_service::BrokerService;
+pub use sync_service::SyncService;
+pub use journal_service::JournalService;
+pub use audit_service::AuditService;
+pub use recurrence_service::RecurrenceService;
+pub use template_service::TemplateService;
+pub use checklist_service::ChecklistService;
+pub use link_service::LinkService;
+pub use tag_service::TagService;
+pub use custom_field_service::CustomFieldService;
@@ -0,0 +1,465 @@
+use crate::db::{todo_series, todos, NewTodo, Todo, TodoSeries};
+use crate::dto::{
+    parse_date, ChangeSource, CreateSeriesInput, RecurrenceRule, SeriesView, StopSeriesInput,
+    TodosChanged, UpdateSeriesInput, UpdateTodoInput, DATE_FORMAT,
+};
+use crate::services::audit_service::AuditWriter;
+use crate::services::journal_service::OP_CREATE;
+use crate::services::{CustomFieldService, TodoService};
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult, TodoInput};
+use chrono::{Days, Local, NaiveDate, SecondsFormat, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 到期日期按本地日期计算
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn rule_of(series: &TodoSeries) -> AppResult<RecurrenceRule> {
+    serde_json::from_str(&series.rule)
+        .map_err(|e| AppError::Validation(format!("重复规则已损坏: {}", e)))
+}
+
+fn rule_json(rule: &RecurrenceRule) -> AppResult<String> {
+    serde_json::to_string(rule)
+        .map_err(|e| AppError::Validation(format!("无法序列化重复规则: {}", e)))
+}
+
+/// 系列中未完成、未删除的实例
+fn open_instances(conn: &mut SqliteConnection, series_id: &str) -> AppResult<Vec<Todo>> {
+    let instances = todos::table
+        .filter(todos::series_id.eq(series_id))
+        .filter(todos::deleted_at.is_null())
+        .filter(todos::status.ne("completed"))
+        .load::<Todo>(conn)?;
+    Ok(instances)
+}
+
+/// 重复任务服务：系列的增删改与实例生成
+pub struct RecurrenceService;
+
+impl RecurrenceService {
+    /// 为系列生成到期日为 `due` 的实例，并把下次生成日期推进到 `due` 之后
+    fn spawn_instance(
+        conn: &mut SqliteConnection,
+        series: &TodoSeries,
+        rule: &RecurrenceRule,
+        due: NaiveDate,
+        audit: &AuditWriter,
+    ) -> AppResult<Todo> {
+        let now = now();
+        let new_todo = NewTodo {
+            id: Uuid::new_v4().to_string(),
+            title: series.title.clone(),
+            status: "pending".to_string(),
+            broker: series.broker.clone(),
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            conclusion: None,
+            deleted_at: None,
+            restored_at: None,
+            restored_by: None,
+            version: 1,
+            series_id: Some(series.id.clone()),
+            due_date: Some(due.format(DATE_FORMAT).to_string()),
+            custom_fields: series.custom_fields.clone(),
+        };
+        diesel::insert_into(todos::table)
+            .values(&new_todo)
+            .execute(conn)?;
+        let todo = todos::table.find(&new_todo.id).first::<Todo>(conn)?;
+        audit.record(conn, OP_CREATE, None, &todo)?;
+
+        diesel::update(todo_series::table.find(&series.id))
+            .set((
+                todo_series::next_due.eq(rule.next_after(due).format(DATE_FORMAT).to_string()),
+                todo_series::updated_at.eq(&now),
+            ))
+            .execute(conn)?;
+
+        tracing::info!("Generated todo {} for series {} due {}", todo.id, series.id, due);
+        Ok(todo)
+    }
+
+    /// 实例被完成后调用：系列仍在进行且没有其他未完成实例时立即生成下一个实例
+    pub(crate) fn on_completed(conn: &mut SqliteConnection, todo: &Todo) -> AppResult<Option<Todo>> {
+        let Some(series_id) = todo.series_id.as_deref() else {
+            return Ok(None);
+        };
+        let series = todo_series::table
+            .find(series_id)
+            .filter(todo_series::active.eq(true))
+            .first::<TodoSeries>(conn)
+            .optional()?;
+        let Some(series) = series else {
+            return Ok(None);
+        };
+        if !open_instances(conn, series_id)?.is_empty() {
+            return Ok(None);
+        }
+
+        let rule = rule_of(&series)?;
+        let due = parse_date(&series.next_due)?;
+        let audit = AuditWriter::new(conn, ChangeSource::Recurrence)?;
+        Self::spawn_instance(conn, &series, &rule, due, &audit).map(Some)
+    }
+
+    /// 创建系列并立即生成第一个实例，返回该实例
+    pub fn create_series(storage: &impl Storage, input: CreateSeriesInput) -> AppResult<Todo> {
+        tracing::debug!("RecurrenceService::create_series - title: {}, rule: {:?}", input.title, input.rule);
+        TodoInput {
+            title: input.title.clone(),
+            broker: input.broker.clone(),
+        }
+        .validate_and_sanitize()?;
+        input.rule.validate()?;
+        let start = match input.start_date.as_deref() {
+            Some(date) => parse_date(date)?,
+            None => today(),
+        };
+
+        let mut conn = storage.connection()?;
+        let custom_fields = match input.custom_fields {
+            Some(ref values) => CustomFieldService::apply_values(&mut conn, "{}", values)?,
+            None => "{}".to_string(),
+        };
+        let now = now();
+        let series = TodoSeries {
+            id: Uuid::new_v4().to_string(),
+            title: input.title.trim().to_string(),
+            broker: input.broker.trim().to_string(),
+            rule: rule_json(&input.rule)?,
+            next_due: start.format(DATE_FORMAT).to_string(),
+            active: true,
+            created_at: now.clone(),
+            updated_at: now,
+            custom_fields,
+        };
+
+        let todo = conn.transaction::<_, AppError, _>(|conn| {
+            diesel::insert_into(todo_series::table)
+                .values(&series)
+                .execute(conn)?;
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            let due = input.rule.first_on_or_after(start);
+            Self::spawn_instance(conn, &series, &input.rule, due, &audit)
+        })?;
+
+        tracing::info!("Created series {} with first todo {}", series.id, todo.id);
+        storage.todos_changed(&TodosChanged::created(todo.clone()));
+        Ok(todo)
+    }
+
+    /// 获取所有系列（最新创建的在前）
+    pub fn list_series(storage: &impl Storage) -> AppResult<Vec<SeriesView>> {
+        let mut conn = storage.connection()?;
+        todo_series::table
+            .order(todo_series::created_at.desc())
+            .load::<TodoSeries>(&mut conn)?
+            .into_iter()
+            .map(SeriesView::try_from)
+            .collect()
+    }
+
+    /// 修改系列；标题、券商同时应用到未完成的实例，规则修改后重新计算下次生成日期
+    pub fn update_series(storage: &impl Storage, input: UpdateSeriesInput) -> AppResult<SeriesView> {
+        tracing::debug!("RecurrenceService::update_series - series_id: {}", input.series_id);
+        if let Some(rule) = &input.rule {
+            rule.validate()?;
+        }
+        let template = UpdateTodoInput {
+            todo_id: String::new(),
+            title: input.title.clone(),
+            status: None,
+            broker: input.broker.clone(),
+            conclusion: None,
+            custom_fields: input.custom_fields.clone(),
+            expected_version: None,
+        };
+        TodoService::validate_update(&template)?;
+
+        let mut conn = storage.connection()?;
+        let mut changes = TodosChanged::default();
+        let series = conn.transaction::<_, AppError, _>(|conn| {
+            let mut series = todo_series::table
+                .find(&input.series_id)
+                .first::<TodoSeries>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::Validation("重复任务系列不存在".to_string()))?;
+
+            if let Some(title) = &input.title {
+                series.title = title.trim().to_string();
+            }
+            if let Some(broker) = &input.broker {
+                series.broker = broker.trim().to_string();
+            }
+            if let Some(values) = &input.custom_fields {
+                series.custom_fields = CustomFieldService::apply_values(conn, &series.custom_fields, values)?;
+            }
+            if let Some(rule) = &input.rule {
+                // 从最近一个实例之后、且不早于今天的第一次发生日期继续
+                let latest_due = todos::table
+                    .filter(todos::series_id.eq(&series.id))
+                    .select(diesel::dsl::max(todos::due_date))
+                    .first::<Option<String>>(conn)?
+                    .map(|date| parse_date(&date))
+                    .transpose()?;
+                let base = latest_due
+                    .and_then(|date| date.checked_add_days(Days::new(1)))
+                    .map_or(today(), |date| date.max(today()));
+                series.rule = rule_json(rule)?;
+                series.next_due = rule.first_on_or_after(base).format(DATE_FORMAT).to_string();
+            }
+            series.updated_at = now();
+
+            diesel::update(todo_series::table.find(&series.id))
+                .set((
+                    todo_series::title.eq(&series.title),
+                    todo_series::broker.eq(&series.broker),
+                    todo_series::rule.eq(&series.rule),
+                    todo_series::next_due.eq(&series.next_due),
+                    todo_series::updated_at.eq(&series.updated_at),
+                    todo_series::custom_fields.eq(&series.custom_fields),
+                ))
+                .execute(conn)?;
+
+            if template.title.is_some() || template.broker.is_some() || template.custom_fields.is_some() {
+                let audit = AuditWriter::new(conn, storage.change_source())?;
+                for instance in open_instances(conn, &series.id)? {
+                    let row = UpdateTodoInput { todo_id: instance.id, ..template.clone() };
+                    let (todo, _) = TodoService::apply_update(conn, &row, &series.updated_at, &audit)?;
+                    changes.updated.push(todo);
+                }
+            }
+            Ok(series)
+        })?;
+
+        tracing::info!("Updated series {}, {} open todos changed", series.id, changes.updated.len());
+        if !changes.is_empty() {
+            storage.todos_changed(&changes);
+        }
+        SeriesView::try_from(series)
+    }
+
+    /// 停止系列，不再生成新实例；可选同时把未完成的实例移入回收站
+    pub fn stop_series(storage: &impl Storage, input: StopSeriesInput) -> AppResult<SeriesView> {
+        tracing::debug!("RecurrenceService::stop_series - series_id: {}", input.series_id);
+        let mut conn = storage.connection()?;
+        let mut changes = TodosChanged::default();
+        let now = now();
+
+        let series = conn.transaction::<_, AppError, _>(|conn| {
+            let affected = diesel::update(todo_series::table.find(&input.series_id))
+                .set((todo_series::active.eq(false), todo_series::updated_at.eq(&now)))
+                .execute(conn)?;
+            if affected == 0 {
+                return Err(AppError::Validation("重复任务系列不存在".to_string()));
+            }
+
+            if input.delete_open {
+                let audit = AuditWriter::new(conn, storage.change_source())?;
+                for instance in open_instances(conn, &input.series_id)? {
+                    if let Some(todo) = TodoService::apply_delete(conn, &instance.id, &now, &audit)? {
+                        changes.deleted.push(todo.id);
+                    }
+                }
+            }
+            let series = todo_series::table
+                .find(&input.series_id)
+                .first::<TodoSeries>(conn)?;
+            Ok(series)
+        })?;
+
+        tracing::info!("Stopped series {}, {} open todos deleted", series.id, changes.deleted.len());
+        if !changes.is_empty() {
+            storage.todos_changed(&changes);
+        }
+        SeriesView::try_from(series)
+    }
+
+    /// 为到期的系列生成实例（后台定时调用）
+    ///
+    /// 错过的多次发生只补生成最近的一次，避免长时间未运行后堆积大量任务。
+    /// 每个系列一个事务，规则损坏等单个系列的错误只记录日志并跳过该系列。
+    pub fn generate_due(storage: &impl Storage) -> AppResult<Vec<Todo>> {
+        let today = today();
+        let today_str = today.format(DATE_FORMAT).to_string();
+        let mut conn = storage.connection()?;
+
+        let due_series = todo_series::table
+            .filter(todo_series::active.eq(true))
+            .filter(todo_series::next_due.le(&today_str))
+            .load::<TodoSeries>(&mut conn)?;
+
+        let mut created = Vec::with_capacity(due_series.len());
+        for series in due_series {
+            let spawned = conn.transaction::<_, AppError, _>(|conn| {
+                let rule = rule_of(&series)?;
+                let mut due = parse_date(&series.next_due)?;
+                while rule.next_after(due) <= today {
+                    due = rule.next_after(due);
+                }
+                let audit = AuditWriter::new(conn, ChangeSource::Recurrence)?;
+                Self::spawn_instance(conn, &series, &rule, due, &audit)
+            });
+            match spawned {
+                Ok(todo) => created.push(todo),
+                Err(e) => tracing::warn!("Skipped recurring series {}: {}", series.id, e),
+            }
+        }
+
+        if !created.is_empty() {
+            tracing::info!("Generated {} recurring todos", created.len());
+            storage.todos_changed(&TodosChanged {
+                created: created.clone(),
+                ..Default::default()
+            });
+        }
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::{CreateCustomFieldInput, FieldType};
+    use crate::services::SyncService;
+    use crate::test_support::memory_pool;
+    use serde_json::{json, Map, Value};
+
+    fn daily_series(storage: &impl Storage) -> Todo {
+        RecurrenceService::create_series(
+            storage,
+            CreateSeriesInput {
+                title: "日报".to_string(),
+                broker: "测试券商".to_string(),
+                rule: RecurrenceRule::Daily { interval: 1 },
+                start_date: None,
+                custom_fields: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn series_id_is_neither_pushed_nor_overwritten() {
+        let pool = memory_pool();
+        let instance = daily_series(&pool);
+        assert!(instance.series_id.is_some());
+
+        let pending = SyncService::pending_changes(&pool, "default", None).unwrap();
+        assert_eq!(pending[0].series_id, None);
+
+        let remote = Todo {
+            title: "远端修改".to_string(),
+            series_id: Some("other-device-series".to_string()),
+            ..instance.clone()
+        };
+        TodoService::upsert_batch(&pool, vec![remote], ChangeSource::Sync).unwrap();
+        let stored = TodoService::get(&pool, &instance.id).unwrap().unwrap();
+        assert_eq!(stored.title, "远端修改");
+        assert_eq!(stored.series_id, instance.series_id);
+    }
+
+    #[test]
+    fn generate_due_skips_series_with_corrupt_rule() {
+        let pool = memory_pool();
+        let broken = daily_series(&pool).series_id.unwrap();
+        let valid = daily_series(&pool).series_id.unwrap();
+        let today = today().format(DATE_FORMAT).to_string();
+        let mut conn = pool.connection().unwrap();
+        diesel::update(todo_series::table)
+            .set(todo_series::next_due.eq(&today))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::update(todo_series::table.find(&broken))
+            .set(todo_series::rule.eq("not a rule"))
+            .execute(&mut conn)
+            .unwrap();
+        drop(conn);
+
+        let created = RecurrenceService::generate_due(&pool).unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].series_id.as_deref(), Some(valid.as_str()));
+
+        let mut conn = pool.connection().unwrap();
+        let next_due = todo_series::table
+            .find(&broken)
+            .select(todo_series::next_due)
+            .first::<String>(&mut conn)
+            .unwrap();
+        assert_eq!(next_due, today);
+    }
+
+    fn fields(value: Value) -> Option<Map<String, Value>> {
+        value.as_object().cloned()
+    }
+
+    fn values(todo: &Todo) -> Value {
+        serde_json::from_str(&todo.custom_fields).unwrap()
+    }
+
+    #[test]
+    fn series_fields_are_copied_into_every_instance() {
+        let pool = memory_pool();
+        CustomFieldService::create(
+            &pool,
+            CreateCustomFieldInput {
+                key: "amount".to_string(),
+                name: "金额".to_string(),
+                field_type: FieldType::Number,
+                options: Vec::new(),
+            },
+        )
+        .unwrap();
+
+        let first = RecurrenceService::create_series(
+            &pool,
+            CreateSeriesInput {
+                title: "月度对账".to_string(),
+                broker: "测试券商".to_string(),
+                rule: RecurrenceRule::Daily { interval: 1 },
+                start_date: None,
+                custom_fields: fields(json!({ "amount": 10 })),
+            },
+        )
+        .unwrap();
+        assert_eq!(values(&first), json!({ "amount": 10 }));
+
+        // 修改系列的字段值同时更新未完成的实例
+        let series_id = first.series_id.clone().unwrap();
+        RecurrenceService::update_series(
+            &pool,
+            UpdateSeriesInput {
+                series_id,
+                title: None,
+                broker: None,
+                rule: None,
+                custom_fields: fields(json!({ "amount": 20 })),
+            },
+        )
+        .unwrap();
+        let first = TodoService::get(&pool, &first.id).unwrap().unwrap();
+        assert_eq!(values(&first), json!({ "amount": 20 }));
+
+        let update = UpdateTodoInput {
+            todo_id: first.id.clone(),
+            title: None,
+            status: Some("completed".to_string()),
+            broker: None,
+            conclusion: Some("已完成".to_string()),
+            custom_fields: None,
+            expected_version: None,
+        };
+        TodoService::update(&pool, update).unwrap();
+        let next = TodoService::latest_with_status(&pool, "pending").unwrap().unwrap();
+        assert_ne!(next.id, first.id);
+        assert_eq!(values(&next), json!({ "amount": 20 }));
+    }
+}
@@ -0,0 +1,331 @@
+use crate::db::{sync_meta, todo_items, todo_links, todo_sync_profiles, todo_tags, todos, undo_journal, Todo};
+use crate::storage::Storage;
+use crate::dto::PurgeReport;
+use crate::utils::{AppError, AppResult};
+use chrono::{Duration, SecondsFormat, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::upsert::excluded;
+
+/// 已确认推送到服务端的最大 `updated_at`（按配置区分：`synced_until:<profile>`）
+const KEY_SYNCED_UNTIL: &str = "synced_until";
+/// 上一次同步返回的 `server_time`，作为下一次拉取的游标（`last_sync:<profile>`）
+const KEY_LAST_SYNC: &str = "last_sync";
+/// 墓碑保留天数
+const KEY_TOMBSTONE_RETENTION_DAYS: &str = "tombstone_retention_days";
+
+pub const DEFAULT_TOMBSTONE_RETENTION_DAYS: u32 = 90;
+
+/// 按 id 绑定、删除的分块大小（远低于 SQLite 变量上限）
+const BIND_CHUNK_SIZE: usize = 500;
+
+fn profile_key(prefix: &str, profile: &str) -> String {
+    format!("{}:{}", prefix, profile)
+}
+
+fn validate_retention_days(days: u32) -> AppResult<u32> {
+    if days == 0 || days > 3650 {
+        return Err(AppError::Validation("保留天数必须在 1-3650 之间".to_string()));
+    }
+    Ok(days)
+}
+
+/// 同步元数据与墓碑清理服务
+pub struct SyncService;
+
+impl SyncService {
+    pub(crate) fn get_meta(conn: &mut SqliteConnection, key: &str) -> AppResult<Option<String>> {
+        let value = sync_meta::table
+            .find(key)
+            .select(sync_meta::value)
+            .first::<String>(conn)
+            .optional()?;
+        Ok(value)
+    }
+
+    pub(crate) fn set_meta(conn: &mut SqliteConnection, key: &str, value: &str) -> AppResult<()> {
+        diesel::insert_into(sync_meta::table)
+            .values((sync_meta::key.eq(key), sync_meta::value.eq(value)))
+            .on_conflict(sync_meta::key)
+            .do_update()
+            .set(sync_meta::value.eq(excluded(sync_meta::value)))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// 记录一次成功同步：`synced_until` 及之前属于该配置的本地变更均已被服务端确认
+    pub fn mark_synced(storage: &impl Storage, profile: &str, synced_until: &str) -> AppResult<String> {
+        let mut conn = storage.connection()?;
+        let key = profile_key(KEY_SYNCED_UNTIL, profile);
+        let current = Self::get_meta(&mut conn, &key)?;
+
+        // 游标只前进不后退
+        let value = match current {
+            Some(current) if current.as_str() >= synced_until => current,
+            _ => {
+                Self::set_meta(&mut conn, &key, synced_until)?;
+                synced_until.to_string()
+            }
+        };
+
+        tracing::debug!("SyncService::mark_synced - profile: {}, synced_until: {}", profile, value);
+        Ok(value)
+    }
+
+    /// 获取同步游标
+    pub fn get_last_sync(storage: &impl Storage, profile: &str) -> AppResult<Option<String>> {
+        let mut conn = storage.connection()?;
+        Self::get_meta(&mut conn, &profile_key(KEY_LAST_SYNC, profile))
+    }
+
+    /// 保存同步游标
+    pub fn set_last_sync(storage: &impl Storage, profile: &str, server_time: &str) -> AppResult<()> {
+        let mut conn = storage.connection()?;
+        Self::set_meta(&mut conn, &profile_key(KEY_LAST_SYNC, profile), server_time)
+    }
+
+    /// 待推送到指定配置的本地变更：`updated_after` 之后、属于该配置或尚未绑定的记录
+    pub fn pending_changes(
+        storage: &impl Storage,
+        profile: &str,
+        updated_after: Option<String>,
+    ) -> AppResult<Vec<Todo>> {
+        let mut conn = storage.connection()?;
+        let other_profiles = todo_sync_profiles::table
+            .filter(todo_sync_profiles::profile.ne(profile))
+            .select(todo_sync_profiles::todo_id);
+
+        let mut query = todos::table
+            .filter(diesel::dsl::not(todos::id.eq_any(other_profiles)))
+            .into_boxed();
+        if let Some(after) = updated_after {
+            query = query.filter(todos::updated_at.gt(after));
+        }
+
+        // 重复系列只保存在本地，不随任务推送
+        let results = query
+            .order(todos::updated_at.asc())
+            .load::<Todo>(&mut conn)?
+            .into_iter()
+            .map(|todo| Todo { series_id: None, ..todo })
+            .collect();
+        Ok(results)
+    }
+
+    /// 将记录绑定到指定配置（推送或拉取成功后调用）
+    pub fn bind(storage: &impl Storage, profile: &str, todo_ids: &[String]) -> AppResult<()> {
+        if todo_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            for chunk in todo_ids.chunks(BIND_CHUNK_SIZE) {
+                let values: Vec<_> = chunk
+                    .iter()
+                    .map(|id| {
+                        (
+                            todo_sync_profiles::todo_id.eq(id),
+                            todo_sync_profiles::profile.eq(profile),
+                        )
+                    })
+                    .collect();
+                diesel::replace_into(todo_sync_profiles::table)
+                    .values(&values)
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// 将所有尚未绑定的记录绑定到指定配置（切换配置前调用，避免带到新服务器）
+    pub fn bind_unbound(storage: &impl Storage, profile: &str) -> AppResult<usize> {
+        let mut conn = storage.connection()?;
+        let count = diesel::sql_query(
+            "INSERT INTO todo_sync_profiles (todo_id, profile) \
+             SELECT id, ? FROM todos \
+             WHERE id NOT IN (SELECT todo_id FROM todo_sync_profiles)",
+        )
+        .bind::<Text, _>(profile)
+        .execute(&mut conn)?;
+
+        tracing::info!("Bound {} unsynced todos to profile {}", count, profile);
+        Ok(count)
+    }
+
+    /// 清除配置的同步游标与记录绑定（服务器地址变更后调用，下次同步按首次同步处理）
+    pub fn reset_profile(storage: &impl Storage, profile: &str) -> AppResult<usize> {
+        let mut conn = storage.connection()?;
+        let unbound = conn.transaction::<_, AppError, _>(|conn| {
+            let keys = [profile_key(KEY_LAST_SYNC, profile), profile_key(KEY_SYNCED_UNTIL, profile)];
+            diesel::delete(sync_meta::table.filter(sync_meta::key.eq_any(&keys))).execute(conn)?;
+            let unbound = diesel::delete(todo_sync_profiles::table.filter(todo_sync_profiles::profile.eq(profile)))
+                .execute(conn)?;
+            Ok(unbound)
+        })?;
+
+        tracing::info!("Reset sync state of profile {}, {} todos unbound", profile, unbound);
+        Ok(unbound)
+    }
+
+    /// 获取墓碑保留天数
+    pub fn get_retention_days(storage: &impl Storage) -> AppResult<u32> {
+        let mut conn = storage.connection()?;
+        let days = Self::get_meta(&mut conn, KEY_TOMBSTONE_RETENTION_DAYS)?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TOMBSTONE_RETENTION_DAYS);
+        Ok(days)
+    }
+
+    /// 设置墓碑保留天数
+    pub fn set_retention_days(storage: &impl Storage, days: u32) -> AppResult<u32> {
+        let days = validate_retention_days(days)?;
+        let mut conn = storage.connection()?;
+        Self::set_meta(&mut conn, KEY_TOMBSTONE_RETENTION_DAYS, &days.to_string())?;
+        tracing::info!("Tombstone retention set to {} days", days);
+        Ok(days)
+    }
+
+    /// 各配置已确认推送到的 `updated_at`（配置名 → 游标）
+    pub(crate) fn synced_cursors(conn: &mut SqliteConnection) -> AppResult<Vec<(String, String)>> {
+        let prefix = format!("{}:", KEY_SYNCED_UNTIL);
+        let cursors = sync_meta::table
+            .filter(sync_meta::key.like(format!("{}%", prefix)))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .map(|(key, value)| (key[prefix.len()..].to_string(), value))
+            .collect();
+        Ok(cursors)
+    }
+
+    /// 物理删除任务及其同步绑定、检查项、关联、标签与撤销记录，按 `BIND_CHUNK_SIZE` 分块
+    pub(crate) fn delete_rows(conn: &mut SqliteConnection, ids: &[String]) -> AppResult<()> {
+        for chunk in ids.chunks(BIND_CHUNK_SIZE) {
+            diesel::delete(todos::table.filter(todos::id.eq_any(chunk))).execute(conn)?;
+            diesel::delete(todo_sync_profiles::table.filter(todo_sync_profiles::todo_id.eq_any(chunk)))
+                .execute(conn)?;
+            diesel::delete(todo_items::table.filter(todo_items::todo_id.eq_any(chunk))).execute(conn)?;
+            diesel::delete(
+                todo_links::table.filter(
+                    todo_links::source_id
+                        .eq_any(chunk)
+                        .or(todo_links::target_id.eq_any(chunk)),
+                ),
+            )
+            .execute(conn)?;
+            diesel::delete(todo_tags::table.filter(todo_tags::todo_id.eq_any(chunk))).execute(conn)?;
+            diesel::delete(undo_journal::table.filter(undo_journal::todo_id.eq_any(chunk)))
+                .execute(conn)?;
+        }
+        Ok(())
+    }
+
+    /// 物理删除超过保留期、且已被所属配置的服务端确认的软删除记录
+    pub fn purge_deleted(storage: &impl Storage, retention_days: Option<u32>) -> AppResult<PurgeReport> {
+        let retention_days = match retention_days {
+            Some(days) => validate_retention_days(days)?,
+            None => Self::get_retention_days(storage)?,
+        };
+        let cutoff = (Utc::now() - Duration::days(i64::from(retention_days)))
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let mut conn = storage.connection()?;
+        let cursors = Self::synced_cursors(&mut conn)?;
+
+        let mut report = PurgeReport {
+            retention_days,
+            cutoff: cutoff.clone(),
+            synced_profiles: cursors.iter().map(|(profile, _)| profile.clone()).collect(),
+            purged_ids: Vec::new(),
+        };
+
+        // 从未确认同步（或未绑定配置）的删除不会被清理，避免丢失尚未推送的删除操作
+        report.purged_ids = conn.transaction::<_, AppError, _>(|conn| {
+            let mut purged = Vec::new();
+            for (profile, synced_until) in &cursors {
+                let bound = todo_sync_profiles::table
+                    .filter(todo_sync_profiles::profile.eq(profile))
+                    .select(todo_sync_profiles::todo_id);
+                let ids = todos::table
+                    .filter(todos::deleted_at.is_not_null())
+                    .filter(todos::deleted_at.lt(&cutoff))
+                    .filter(todos::updated_at.le(synced_until))
+                    .filter(todos::id.eq_any(bound))
+                    .select(todos::id)
+                    .load::<String>(conn)?;
+
+                Self::delete_rows(conn, &ids)?;
+                purged.extend(ids);
+            }
+            Ok(purged)
+        })?;
+
+        tracing::info!(
+            "Purged {} tombstones (cutoff={}, profiles={:?})",
+            report.purged_ids.len(), report.cutoff, report.synced_profiles
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::ChangeSource;
+    use crate::services::TodoService;
+    use crate::test_support::{memory_pool, remote_todo};
+
+    #[test]
+    fn purge_deleted_rejects_out_of_range_retention() {
+        let pool = memory_pool();
+        for days in [0, 3651] {
+            assert!(matches!(
+                SyncService::purge_deleted(&pool, Some(days)),
+                Err(AppError::Validation(_))
+            ));
+        }
+        assert!(SyncService::purge_deleted(&pool, Some(1)).is_ok());
+    }
+
+    #[test]
+    fn reset_profile_clears_only_that_profile() {
+        let pool = memory_pool();
+        let rows = vec![
+            remote_todo("a", "甲", "2026-01-01T00:00:00Z"),
+            remote_todo("b", "乙", "2026-01-01T00:00:00Z"),
+        ];
+        TodoService::upsert_batch(&pool, rows, ChangeSource::Sync).unwrap();
+        SyncService::bind(&pool, "old", &["a".to_string()]).unwrap();
+        SyncService::bind(&pool, "other", &["b".to_string()]).unwrap();
+        for profile in ["old", "other"] {
+            SyncService::set_last_sync(&pool, profile, "2026-01-02T00:00:00Z").unwrap();
+            SyncService::mark_synced(&pool, profile, "2026-01-02T00:00:00Z").unwrap();
+        }
+
+        assert_eq!(SyncService::reset_profile(&pool, "old").unwrap(), 1);
+        assert_eq!(SyncService::get_last_sync(&pool, "old").unwrap(), None);
+        assert!(SyncService::get_last_sync(&pool, "other").unwrap().is_some());
+
+        // 解除绑定的记录重新推送到该配置，其他配置的记录不受影响
+        let pending = SyncService::pending_changes(&pool, "old", None).unwrap();
+        assert_eq!(pending.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec!["a"]);
+        let mut conn = pool.connection().unwrap();
+        assert!(SyncService::get_meta(&mut conn, "synced_until:old").unwrap().is_none());
+        assert!(SyncService::get_meta(&mut conn, "synced_until:other").unwrap().is_some());
+    }
+
+    #[test]
+    fn delete_rows_handles_more_ids_than_one_chunk() {
+        let pool = memory_pool();
+        let rows = (0..BIND_CHUNK_SIZE * 2 + 1)
+            .map(|i| remote_todo(&format!("00000000-0000-4000-8000-{:012}", i), "任务", "2026-01-01T00:00:00Z"))
+            .collect::<Vec<_>>();
+        let ids = rows.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+        TodoService::upsert_batch(&pool, rows, ChangeSource::Sync).unwrap();
+
+        let mut conn = pool.connection().unwrap();
+        SyncService::delete_rows(&mut conn, &ids).unwrap();
+        assert_eq!(todos::table.count().get_result::<i64>(&mut conn).unwrap(), 0);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::db::{tags, todo_tags, todos, NewTodoTag, Tag};
+use crate::dto::{
+    CreateTagInput, DeleteTagInput, MergeTagsInput, SetTodoTagsInput, TagView, UpdateTagInput,
+};
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult};
+use chrono::{SecondsFormat, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// 单个任务最多的标签数
+const MAX_TAGS_PER_TODO: usize = 20;
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn validate_name(name: &str) -> AppResult<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > 50 {
+        return Err(AppError::Validation("标签名称长度必须在 1-50 字符之间".to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// 颜色须为 #RRGGBB，空字符串表示不设置颜色
+fn validate_color(color: &str) -> AppResult<Option<String>> {
+    let trimmed = color.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let valid = trimmed.len() == 7
+        && trimmed.starts_with('#')
+        && trimmed[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(AppError::Validation("标签颜色格式应为 #RRGGBB".to_string()));
+    }
+    Ok(Some(trimmed.to_lowercase()))
+}
+
+fn find_tag(conn: &mut SqliteConnection, tag_id: &str) -> AppResult<Tag> {
+    tags::table
+        .find(tag_id)
+        .first::<Tag>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::Validation("标签不存在".to_string()))
+}
+
+/// 名称（不区分大小写）被其他标签占用时返回错误
+fn ensure_unique_name(conn: &mut SqliteConnection, name: &str, except_id: Option<&str>) -> AppResult<()> {
+    let existing = tags::table
+        .filter(tags::name.eq(name))
+        .select(tags::id)
+        .first::<String>(conn)
+        .optional()?;
+    match existing {
+        Some(id) if Some(id.as_str()) != except_id => Err(AppError::Validation(format!(
+            "标签「{}」已存在，如需合并请使用合并功能",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[derive(QueryableByName)]
+struct TagCount {
+    #[diesel(sql_type = Text)]
+    tag_id: String,
+    #[diesel(sql_type = BigInt)]
+    todo_count: i64,
+}
+
+/// 标签服务（标签只保存在本地，不参与同步）
+pub struct TagService;
+
+impl TagService {
+    /// 获取所有标签及其未删除任务数（按名称排序）
+    pub fn list(storage: &impl Storage) -> AppResult<Vec<TagView>> {
+        let mut conn = storage.connection()?;
+        let counts = diesel::sql_query(
+            "SELECT tt.tag_id AS tag_id, COUNT(*) AS todo_count \
+             FROM todo_tags tt JOIN todos t ON t.id = tt.todo_id \
+             WHERE t.deleted_at IS NULL \
+             GROUP BY tt.tag_id",
+        )
+        .load::<TagCount>(&mut conn)?
+        .into_iter()
+        .map(|row| (row.tag_id, row.todo_count))
+        .collect::<HashMap<_, _>>();
+
+        let tags = tags::table
+            .order(tags::name.asc())
+            .load::<Tag>(&mut conn)?
+            .into_iter()
+            .map(|tag| TagView {
+                todo_count: counts.get(&tag.id).copied().unwrap_or(0),
+                id: tag.id,
+                name: tag.name,
+                color: tag.color,
+                created_at: tag.created_at,
+                updated_at: tag.updated_at,
+            })
+            .collect();
+        Ok(tags)
+    }
+
+    /// 创建标签
+    pub fn create(storage: &impl Storage, input: CreateTagInput) -> AppResult<Tag> {
+        let name = validate_name(&input.name)?;
+        let color = input.color.as_deref().map(validate_color).transpose()?.flatten();
+        let now = now();
+        let tag = Tag {
+            id: Uuid::new_v4().to_string(),
+            name,
+            color,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            ensure_unique_name(conn, &tag.name, None)?;
+            diesel::insert_into(tags::table).values(&tag).execute(conn)?;
+            Ok(())
+        })?;
+
+        tracing::info!("Created tag {} ({})", tag.id, tag.name);
+        Ok(tag)
+    }
+
+    /// 修改标签名称或颜色
+    pub fn update(storage: &impl Storage, input: UpdateTagInput) -> AppResult<Tag> {
+        let name = input.name.as_deref().map(validate_name).transpose()?;
+        let color = input.color.as_deref().map(validate_color).transpose()?;
+
+        let mut conn = storage.connection()?;
+        let tag = conn.transaction::<_, AppError, _>(|conn| {
+            let mut tag = find_tag(conn, &input.tag_id)?;
+            if let Some(name) = name {
+                ensure_unique_name(conn, &name, Some(&tag.id))?;
+                tag.name = name;
+            }
+            if let Some(color) = color {
+                tag.color = color;
+            }
+            tag.updated_at = now();
+            diesel::update(tags::table.find(&tag.id))
+                .set((
+                    tags::name.eq(&tag.name),
+                    tags::color.eq(&tag.color),
+                    tags::updated_at.eq(&tag.updated_at),
+                ))
+                .execute(conn)?;
+            Ok(tag)
+        })?;
+
+        tracing::info!("Updated tag {} ({})", tag.id, tag.name);
+        Ok(tag)
+    }
+
+    /// 删除标签及其与任务的关联
+    pub fn delete(storage: &impl Storage, input: DeleteTagInput) -> AppResult<()> {
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let affected = diesel::delete(tags::table.find(&input.tag_id)).execute(conn)?;
+            if affected == 0 {
+                return Err(AppError::Validation("标签不存在".to_string()));
+            }
+            diesel::delete(todo_tags::table.filter(todo_tags::tag_id.eq(&input.tag_id)))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        tracing::info!("Deleted tag {}", input.tag_id);
+        Ok(())
+    }
+
+    /// 合并标签：源标签的任务改挂目标标签（已有的不重复），然后删除源标签
+    pub fn merge(storage: &impl Storage, input: MergeTagsInput) -> AppResult<Tag> {
+        let mut sources = input
+            .source_ids
+            .into_iter()
+            .filter(|id| *id != input.target_id)
+            .collect::<Vec<_>>();
+        sources.sort();
+        sources.dedup();
+        if sources.is_empty() {
+            return Err(AppError::Validation("请选择要合并的标签".to_string()));
+        }
+
+        let mut conn = storage.connection()?;
+        let target = conn.transaction::<_, AppError, _>(|conn| {
+            let target = find_tag(conn, &input.target_id)?;
+            for source in &sources {
+                find_tag(conn, source)?;
+            }
+
+            let now = now();
+            let moved = todo_tags::table
+                .filter(todo_tags::tag_id.eq_any(&sources))
+                .select(todo_tags::todo_id)
+                .distinct()
+                .load::<String>(conn)?
+                .into_iter()
+                .map(|todo_id| NewTodoTag {
+                    todo_id,
+                    tag_id: target.id.clone(),
+                    created_at: now.clone(),
+                })
+                .collect::<Vec<_>>();
+            for chunk in moved.chunks(500) {
+                diesel::insert_or_ignore_into(todo_tags::table)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+
+            diesel::delete(todo_tags::table.filter(todo_tags::tag_id.eq_any(&sources)))
+                .execute(conn)?;
+            diesel::delete(tags::table.filter(tags::id.eq_any(&sources))).execute(conn)?;
+            Ok(target)
+        })?;
+
+        tracing::info!("Merged {} tags into {} ({})", sources.len(), target.id, target.name);
+        Ok(target)
+    }
+
+    /// 设置任务的全部标签，返回设置后的标签 id
+    pub fn set_todo_tags(storage: &impl Storage, input: SetTodoTagsInput) -> AppResult<Vec<String>> {
+        let mut seen = HashSet::new();
+        let tag_ids = input
+            .tag_ids
+            .into_iter()
+            .filter(|id| seen.insert(id.clone()))
+            .collect::<Vec<_>>();
+        if tag_ids.len() > MAX_TAGS_PER_TODO {
+            return Err(AppError::Validation(format!("每个任务最多 {} 个标签", MAX_TAGS_PER_TODO)));
+        }
+
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let exists = todos::table
+                .find(&input.todo_id)
+                .filter(todos::deleted_at.is_null())
+                .count()
+                .get_result::<i64>(conn)?;
+            if exists == 0 {
+                return Err(AppError::Validation("任务不存在".to_string()));
+            }
+            let known = tags::table
+                .filter(tags::id.eq_any(&tag_ids))
+                .count()
+                .get_result::<i64>(conn)?;
+            if known != tag_ids.len() as i64 {
+                return Err(AppError::Validation("标签不存在".to_string()));
+            }
+
+            diesel::delete(todo_tags::table.filter(todo_tags::todo_id.eq(&input.todo_id)))
+                .execute(conn)?;
+            let now = now();
+            let rows = tag_ids
+                .iter()
+                .map(|tag_id| NewTodoTag {
+                    todo_id: input.todo_id.clone(),
+                    tag_id: tag_id.clone(),
+                    created_at: now.clone(),
+                })
+                .collect::<Vec<_>>();
+            diesel::insert_into(todo_tags::table).values(&rows).execute(conn)?;
+            Ok(())
+        })?;
+
+        tracing::debug!("Set {} tags on todo {}", tag_ids.len(), input.todo_id);
+        Ok(tag_ids)
+    }
+
+    /// 所有任务的标签（任务 id → 标签 id 列表）
+    pub fn todo_tag_map(storage: &impl Storage) -> AppResult<HashMap<String, Vec<String>>> {
+        let mut conn = storage.connection()?;
+        let rows = todo_tags::table
+            .order((todo_tags::todo_id.asc(), todo_tags::created_at.asc()))
+            .select((todo_tags::todo_id, todo_tags::tag_id))
+            .load::<(String, String)>(&mut conn)?;
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (todo_id, tag_id) in rows {
+            map.entry(todo_id).or_default().push(tag_id);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Todo;
+    use crate::dto::{ListTodosInput, SearchTodoInput};
+    use crate::services::TodoService;
+    use crate::test_support::{create_todo, memory_pool};
+
+    #[test]
+    fn list_and_search_return_todos_with_any_tag_once() {
+        let pool = memory_pool();
+        let tag = |name: &str| {
+            let input = CreateTagInput { name: name.to_string(), color: None };
+            TagService::create(&pool, input).unwrap().id
+        };
+        let (red, blue) = (tag("红"), tag("蓝"));
+        let both = create_todo(&pool, "两个标签");
+        create_todo(&pool, "没有标签");
+        let input = SetTodoTagsInput { todo_id: both.id.clone(), tag_ids: vec![red.clone(), blue.clone()] };
+        TagService::set_todo_tags(&pool, input).unwrap();
+
+        let tag_ids = vec![red, blue];
+        let ids = |todos: Vec<Todo>| todos.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        let list = ListTodosInput { tag_ids: tag_ids.clone(), field_filter: None };
+        assert_eq!(ids(TodoService::list(&pool, list).unwrap()), vec![both.id.clone()]);
+
+        let search = SearchTodoInput { query: "标签".to_string(), tag_ids, field_filter: None };
+        assert_eq!(ids(TodoService::search(&pool, search).unwrap()), vec![both.id]);
+    }
+}
@@ -0,0 +1,316 @@
+use crate::db::{todo_templates, NewTodo, Todo, TodoTemplate};
+use crate::dto::{
+    CreateTemplateInput, DeleteTemplateInput, InstantiateTemplateInput, TemplateExport,
+    TemplateExportItem, TemplateView, TodosChanged, UpdateTemplateInput, DATE_FORMAT,
+    PLACEHOLDER_BROKER, PLACEHOLDER_DATE, TEMPLATE_EXPORT_VERSION,
+};
+use crate::services::audit_service::AuditWriter;
+use crate::services::TodoService;
+use crate::storage::Storage;
+use crate::utils::{AppError, AppResult, TodoInput};
+use chrono::{Local, SecondsFormat, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// 单个模板最多包含的任务数
+const MAX_TEMPLATE_TASKS: usize = 100;
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn validate_name(name: &str) -> AppResult<()> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > 100 {
+        return Err(AppError::Validation("模板名称长度必须在 1-100 字符之间".to_string()));
+    }
+    Ok(())
+}
+
+/// 模板任务只能是待办或进行中（已完成需要结论）
+fn validate_status(status: &str) -> AppResult<()> {
+    match status {
+        "pending" | "in_progress" => Ok(()),
+        _ => Err(AppError::Validation("模板任务的初始状态只能是待办或进行中".to_string())),
+    }
+}
+
+/// 校验任务标题，并拒绝未知的 `{...}` 占位符（多为拼写错误）
+fn validate_tasks(tasks: &[String]) -> AppResult<Vec<String>> {
+    if tasks.is_empty() || tasks.len() > MAX_TEMPLATE_TASKS {
+        return Err(AppError::Validation(format!("模板任务数必须在 1-{} 之间", MAX_TEMPLATE_TASKS)));
+    }
+    tasks
+        .iter()
+        .map(|task| {
+            let trimmed = task.trim();
+            if trimmed.is_empty() || trimmed.len() > 500 {
+                return Err(AppError::Validation("标题长度必须在 1-500 字符之间".to_string()));
+            }
+            let mut rest = trimmed;
+            while let Some(start) = rest.find('{') {
+                let Some(len) = rest[start..].find('}') else { break };
+                let placeholder = &rest[start..start + len + 1];
+                if placeholder != PLACEHOLDER_BROKER && placeholder != PLACEHOLDER_DATE {
+                    return Err(AppError::Validation(format!(
+                        "未知的占位符 {}，可用 {} 和 {}",
+                        placeholder, PLACEHOLDER_BROKER, PLACEHOLDER_DATE
+                    )));
+                }
+                rest = &rest[start + len + 1..];
+            }
+            Ok(trimmed.to_string())
+        })
+        .collect()
+}
+
+fn tasks_json(tasks: &[String]) -> AppResult<String> {
+    serde_json::to_string(tasks)
+        .map_err(|e| AppError::Validation(format!("无法序列化模板任务: {}", e)))
+}
+
+/// 模板名称已存在时给出可读的错误
+fn ensure_unique_name(conn: &mut SqliteConnection, name: &str, except_id: Option<&str>) -> AppResult<()> {
+    let existing = todo_templates::table
+        .filter(todo_templates::name.eq(name))
+        .select(todo_templates::id)
+        .first::<String>(conn)
+        .optional()?;
+    match existing {
+        Some(id) if Some(id.as_str()) != except_id => {
+            Err(AppError::Validation(format!("模板「{}」已存在", name)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 任务模板服务
+pub struct TemplateService;
+
+impl TemplateService {
+    /// 获取所有模板（按名称排序）
+    pub fn list(storage: &impl Storage) -> AppResult<Vec<TemplateView>> {
+        let mut conn = storage.connection()?;
+        todo_templates::table
+            .order(todo_templates::name.asc())
+            .load::<TodoTemplate>(&mut conn)?
+            .into_iter()
+            .map(TemplateView::try_from)
+            .collect()
+    }
+
+    /// 创建模板
+    pub fn create(storage: &impl Storage, input: CreateTemplateInput) -> AppResult<TemplateView> {
+        tracing::debug!("TemplateService::create - name: {}, {} tasks", input.name, input.tasks.len());
+        validate_name(&input.name)?;
+        let default_status = input.default_status.unwrap_or_else(|| "pending".to_string());
+        validate_status(&default_status)?;
+        let tasks = validate_tasks(&input.tasks)?;
+
+        let now = now();
+        let template = TodoTemplate {
+            id: Uuid::new_v4().to_string(),
+            name: input.name.trim().to_string(),
+            default_status,
+            tasks: tasks_json(&tasks)?,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+
+        let mut conn = storage.connection()?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            ensure_unique_name(conn, &template.name, None)?;
+            diesel::insert_into(todo_templates::table)
+                .values(&template)
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        tracing::info!("Created template {} ({})", template.id, template.name);
+        TemplateView::try_from(template)
+    }
+
+    /// 修改模板
+    pub fn update(storage: &impl Storage, input: UpdateTemplateInput) -> AppResult<TemplateView> {
+        tracing::debug!("TemplateService::update - template_id: {}", input.template_id);
+        if let Some(name) = &input.name {
+            validate_name(name)?;
+        }
+        if let Some(status) = &input.default_status {
+            validate_status(status)?;
+        }
+        let tasks = input.tasks.as_deref().map(validate_tasks).transpose()?;
+
+        let mut conn = storage.connection()?;
+        let template = conn.transaction::<_, AppError, _>(|conn| {
+            let mut template = todo_templates::table
+                .find(&input.template_id)
+                .first::<TodoTemplate>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::Validation("模板不存在".to_string()))?;
+
+            if let Some(name) = &input.name {
+                template.name = name.trim().to_string();
+                ensure_unique_name(conn, &template.name, Some(&template.id))?;
+            }
+            if let Some(status) = &input.default_status {
+                template.default_status = status.clone();
+            }
+            if let Some(tasks) = &tasks {
+                template.tasks = tasks_json(tasks)?;
+            }
+            template.updated_at = now();
+
+            diesel::update(todo_templates::table.find(&template.id))
+                .set((
+                    todo_templates::name.eq(&template.name),
+                    todo_templates::default_status.eq(&template.default_status),
+                    todo_templates::tasks.eq(&template.tasks),
+                    todo_templates::updated_at.eq(&template.updated_at),
+                ))
+                .execute(conn)?;
+            Ok(template)
+        })?;
+
+        tracing::info!("Updated template {}", template.id);
+        TemplateView::try_from(template)
+    }
+
+    /// 删除模板（已创建的任务不受影响）
+    pub fn delete(storage: &impl Storage, input: DeleteTemplateInput) -> AppResult<()> {
+        let mut conn = storage.connection()?;
+        let affected = diesel::delete(todo_templates::table.find(&input.template_id))
+            .execute(&mut conn)?;
+        if affected == 0 {
+            return Err(AppError::Validation("模板不存在".to_string()));
+        }
+        tracing::info!("Deleted template {}", input.template_id);
+        Ok(())
+    }
+
+    /// 按模板为券商创建全部任务（一个事务，任一失败则全部不创建）
+    ///
+    /// 标题中的 `{broker}` 替换为券商名称，`{date}` 替换为今天的本地日期。
+    pub fn instantiate(storage: &impl Storage, input: InstantiateTemplateInput) -> AppResult<Vec<Todo>> {
+        tracing::debug!(
+            "TemplateService::instantiate - template_id: {}, broker: {}",
+            input.template_id, input.broker
+        );
+        let broker = input.broker.trim().to_string();
+        let mut conn = storage.connection()?;
+        let template = todo_templates::table
+            .find(&input.template_id)
+            .first::<TodoTemplate>(&mut conn)
+            .optional()?
+            .ok_or_else(|| AppError::Validation("模板不存在".to_string()))?;
+        let template = TemplateView::try_from(template)?;
+
+        let date = Local::now().date_naive().format(DATE_FORMAT).to_string();
+        let titles = template
+            .tasks
+            .iter()
+            .map(|task| {
+                let title = task
+                    .replace(PLACEHOLDER_BROKER, &broker)
+                    .replace(PLACEHOLDER_DATE, &date);
+                TodoInput { title: title.clone(), broker: broker.clone() }.validate_and_sanitize()?;
+                Ok(title)
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        // 所有任务使用同一时间戳；列表在同一秒内按插入倒序排列，所以倒序插入以保持模板中的顺序
+        let now = now();
+        let mut created = conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            titles
+                .into_iter()
+                .rev()
+                .map(|title| {
+                    let new_todo = NewTodo {
+                        id: Uuid::new_v4().to_string(),
+                        title,
+                        status: template.default_status.clone(),
+                        broker: broker.clone(),
+                        created_at: now.clone(),
+                        updated_at: now.clone(),
+                        conclusion: None,
+                        deleted_at: None,
+                        restored_at: None,
+                        restored_by: None,
+                        version: 1,
+                        series_id: None,
+                        due_date: None,
+                        custom_fields: "{}".to_string(),
+                    };
+                    TodoService::apply_create(conn, &new_todo, &audit)
+                })
+                .collect::<AppResult<Vec<_>>>()
+        })?;
+        created.reverse();
+
+        tracing::info!(
+            "Instantiated template {} for broker {}: {} todos",
+            template.name, broker, created.len()
+        );
+        storage.todos_changed(&TodosChanged {
+            created: created.clone(),
+            ..Default::default()
+        });
+        Ok(created)
+    }
+
+    /// 导出所有模板为 JSON
+    pub fn export_json(storage: &impl Storage) -> AppResult<String> {
+        let templates = Self::list(storage)?
+            .into_iter()
+            .map(|t| TemplateExportItem {
+                name: t.name,
+                default_status: t.default_status,
+                tasks: t.tasks,
+            })
+            .collect::<Vec<_>>();
+        let export = TemplateExport {
+            version: TEMPLATE_EXPORT_VERSION,
+            exported_at: now(),
+            templates,
+        };
+        tracing::info!("Exported {} templates", export.templates.len());
+        serde_json::to_string_pretty(&export)
+            .map_err(|e| AppError::Validation(format!("无法导出模板: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_pool;
+
+    #[test]
+    fn instantiate_keeps_template_order_in_the_list() {
+        let pool = memory_pool();
+        let tasks = ["第一步 {broker}", "第二步", "第三步"];
+        let template = TemplateService::create(
+            &pool,
+            CreateTemplateInput {
+                name: "开户".to_string(),
+                default_status: None,
+                tasks: tasks.iter().map(|t| t.to_string()).collect(),
+            },
+        )
+        .unwrap();
+
+        let created = TemplateService::instantiate(
+            &pool,
+            InstantiateTemplateInput { template_id: template.id, broker: "测试券商".to_string() },
+        )
+        .unwrap();
+        // 时间戳不能超前，否则同步游标会越过之后的本地修改
+        let finished = now();
+        assert!(created.iter().all(|todo| todo.updated_at <= finished));
+
+        let expected = vec!["第一步 测试券商", "第二步", "第三步"];
+        let titles = |todos: Vec<Todo>| todos.into_iter().map(|todo| todo.title).collect::<Vec<_>>();
+        assert_eq!(titles(created), expected);
+        assert_eq!(titles(TodoService::get_all(&pool).unwrap()), expected);
+    }
+}
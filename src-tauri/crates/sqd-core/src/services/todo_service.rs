@@ -0,0 +1,905 @@
+use crate::db::{NewTodo, Todo, UpdateTodo, todo_sync_profiles, todo_tags, todos};
+use crate::services::audit_service::{AuditWriter, OP_RESTORE};
+use crate::services::journal_service::{JournalService, OP_CREATE, OP_DELETE, OP_UPDATE};
+use crate::services::{CustomFieldService, LinkService, RecurrenceService, SyncService};
+use crate::storage::Storage;
+use crate::dto::{BulkDeleteTodosInput, BulkItemResult, BulkUpdateTodosInput, ChangeSource, CreateTodoInput, UpdateTodoInput, DeleteTodoInput, EmptyTrashReport, ListTodosInput, RestoreTodoInput, SearchTodoInput, TodosChanged, UpsertSummary};
+use crate::utils::{AppError, AppResult, TodoInput, current_actor, escape_like_pattern};
+use chrono::{Utc, SecondsFormat};
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::upsert::excluded;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// 每条多行 INSERT 的行数（行数 × 列数远低于 SQLite 变量上限）
+const UPSERT_CHUNK_SIZE: usize = 500;
+/// 单次批量更新/删除的最大任务数
+const MAX_BULK_SIZE: usize = 1000;
+
+/// 列表的次要排序键：`updated_at` 只精确到秒，同一秒内后插入的任务排在前面
+fn rowid() -> SqlLiteral<BigInt> {
+    sql("todos.rowid")
+}
+
+/// Todo 业务逻辑服务
+pub struct TodoService;
+
+impl TodoService {
+    /// 创建新的 Todo
+    pub fn create(storage: &impl Storage, input: CreateTodoInput) -> AppResult<Todo> {
+        tracing::debug!(
+            "TodoService::create - title: {}, status: {}, broker: {}",
+            input.title, input.status, input.broker
+        );
+
+        // 验证输入
+        let validation_input = TodoInput {
+            title: input.title.clone(),
+            broker: input.broker.clone(),
+        };
+        validation_input.validate_and_sanitize()?;
+
+        if input.status == "completed" && input.conclusion.as_ref().map(|s| s.trim().is_empty()).unwrap_or(true) {
+            return Err(AppError::Validation("已完成状态必须填写结论".to_string()));
+        }
+
+        let mut conn = storage.connection()?;
+        let custom_fields = match input.custom_fields {
+            Some(ref values) => CustomFieldService::apply_values(&mut conn, "{}", values)?,
+            None => "{}".to_string(),
+        };
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let id = Uuid::new_v4().to_string();
+
+        let new_todo = NewTodo {
+            id,
+            title: input.title.trim().to_string(),
+            status: input.status,
+            broker: input.broker.trim().to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+            conclusion: input.conclusion.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+            deleted_at: None,
+            restored_at: None,
+            restored_by: None,
+            version: 1,
+            series_id: None,
+            due_date: None,
+            custom_fields,
+        };
+
+        let todo = conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            Self::apply_create(conn, &new_todo, &audit)
+        })?;
+
+        tracing::info!("Created todo: id={}, title={}", todo.id, todo.title);
+        storage.todos_changed(&TodosChanged::created(todo.clone()));
+        Ok(todo)
+    }
+
+    /// 在事务内插入一行并写入撤销与审计记录
+    pub(crate) fn apply_create(
+        conn: &mut SqliteConnection,
+        new_todo: &NewTodo,
+        audit: &AuditWriter,
+    ) -> AppResult<Todo> {
+        diesel::insert_into(todos::table)
+            .values(new_todo)
+            .execute(conn)?;
+
+        tracing::debug!("Todo inserted successfully");
+
+        let todo = todos::table
+            .find(&new_todo.id)
+            .first::<Todo>(conn)?;
+        JournalService::record(conn, OP_CREATE, None, &todo)?;
+        audit.record(conn, OP_CREATE, None, &todo)?;
+        Ok(todo)
+    }
+
+    /// 获取所有 Todos
+    pub fn get_all(storage: &impl Storage) -> AppResult<Vec<Todo>> {
+        tracing::debug!("TodoService::get_all");
+        let mut conn = storage.connection()?;
+
+        let todos_list = todos::table
+            .filter(todos::deleted_at.is_null())
+            .order((todos::updated_at.desc(), rowid().desc()))
+            .load::<Todo>(&mut conn)?;
+        tracing::debug!("Retrieved {} todos", todos_list.len());
+        Ok(todos_list)
+    }
+
+    /// 按 id 获取未删除的 Todo
+    pub fn get(storage: &impl Storage, todo_id: &str) -> AppResult<Option<Todo>> {
+        let mut conn = storage.connection()?;
+        let todo = todos::table
+            .find(todo_id)
+            .filter(todos::deleted_at.is_null())
+            .first::<Todo>(&mut conn)
+            .optional()?;
+        Ok(todo)
+    }
+
+    /// 指定状态中最近更新的未删除 Todo
+    pub fn latest_with_status(storage: &impl Storage, status: &str) -> AppResult<Option<Todo>> {
+        let mut conn = storage.connection()?;
+        let todo = todos::table
+            .filter(todos::deleted_at.is_null())
+            .filter(todos::status.eq(status))
+            .order((todos::updated_at.desc(), rowid().desc()))
+            .first::<Todo>(&mut conn)
+            .optional()?;
+        Ok(todo)
+    }
+
+    /// 按标签与自定义字段过滤获取 Todos（条件都为空时等同于 `get_all`）
+    pub fn list(storage: &impl Storage, input: ListTodosInput) -> AppResult<Vec<Todo>> {
+        tracing::debug!("TodoService::list - {} tags, field filter: {:?}", input.tag_ids.len(), input.field_filter);
+        let mut conn = storage.connection()?;
+
+        let mut query = todos::table
+            .filter(todos::deleted_at.is_null())
+            .order((todos::updated_at.desc(), rowid().desc()))
+            .into_boxed();
+        if !input.tag_ids.is_empty() {
+            let tagged = todo_tags::table
+                .filter(todo_tags::tag_id.eq_any(&input.tag_ids))
+                .select(todo_tags::todo_id);
+            query = query.filter(todos::id.eq_any(tagged));
+        }
+        if let Some(filter) = &input.field_filter {
+            query = CustomFieldService::filter_todos(query, filter)?;
+        }
+        let todos_list = query.load::<Todo>(&mut conn)?;
+        tracing::debug!("Retrieved {} todos", todos_list.len());
+        Ok(todos_list)
+    }
+
+    /// 校验更新字段（与当前行无关的部分）
+    pub(crate) fn validate_update(input: &UpdateTodoInput) -> AppResult<()> {
+        if let Some(ref title) = input.title {
+            let trimmed = title.trim();
+            if trimmed.is_empty() || trimmed.len() > 500 {
+                return Err(AppError::Validation("标题长度必须在 1-500 字符之间".to_string()));
+            }
+        }
+
+        if let Some(ref broker) = input.broker {
+            let trimmed = broker.trim();
+            if trimmed.is_empty() || trimmed.len() > 100 {
+                return Err(AppError::Validation("券商名称长度必须在 1-100 字符之间".to_string()));
+            }
+        }
+
+        if let Some(ref conclusion) = input.conclusion {
+            if !conclusion.trim().is_empty() && conclusion.len() > 2000 {
+                return Err(AppError::Validation("结论长度不能超过 2000 字符".to_string()));
+            }
+        }
+
+        if input.status.as_deref() == Some("completed")
+            && input.conclusion.as_ref().map(|s| s.trim().is_empty()).unwrap_or(true)
+        {
+            return Err(AppError::Validation("已完成状态必须填写结论".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 在事务内更新一行并写入撤销与审计记录，行不存在或已在回收站时返回 NotFound
+    ///
+    /// 重复任务的实例被完成时同时生成下一个实例，作为第二个返回值。
+    pub(crate) fn apply_update(
+        conn: &mut SqliteConnection,
+        input: &UpdateTodoInput,
+        now: &str,
+        audit: &AuditWriter,
+    ) -> AppResult<(Todo, Option<Todo>)> {
+        let target = todos::table
+            .find(&input.todo_id)
+            .filter(todos::deleted_at.is_null());
+        let before = target.first::<Todo>(conn)?;
+
+        let conclusion = input
+            .conclusion
+            .as_ref()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        // 检查项未全部完成时不能完成任务
+        if input.status.as_deref() == Some("completed")
+            && before.status != "completed"
+            && before.items_done < before.items_total
+        {
+            return Err(AppError::Validation(format!(
+                "还有 {} 个检查项未完成，请先完成或删除",
+                before.items_total - before.items_done
+            )));
+        }
+
+        // 被未完成的任务阻塞时不能开始
+        if input.status.as_deref() == Some("in_progress") && before.status != "in_progress" {
+            let blockers = LinkService::open_blockers(conn, &before.id)?;
+            if !blockers.is_empty() {
+                let titles = blockers
+                    .iter()
+                    .map(|todo| format!("「{}」", todo.title))
+                    .collect::<Vec<_>>()
+                    .join("、");
+                return Err(AppError::Validation(format!("任务被未完成的任务阻塞：{}", titles)));
+            }
+        }
+
+        let custom_fields = match input.custom_fields {
+            Some(ref values) => Some(CustomFieldService::apply_values(conn, &before.custom_fields, values)?),
+            None => None,
+        };
+
+        let update_todo = UpdateTodo {
+            title: input.title.as_ref().map(|t| t.trim().to_string()),
+            status: input.status.clone(),
+            broker: input.broker.as_ref().map(|b| b.trim().to_string()),
+            updated_at: now.to_string(),
+            conclusion,
+            deleted_at: None,
+            custom_fields,
+        };
+
+        // 提供 expected_version 时只更新版本未变的行（乐观并发控制）
+        let changes = (&update_todo, todos::version.eq(todos::version + 1));
+        let affected = match input.expected_version {
+            Some(version) => diesel::update(target.filter(todos::version.eq(version)))
+                .set(changes)
+                .execute(conn)?,
+            None => diesel::update(target).set(changes).execute(conn)?,
+        };
+
+        if affected == 0 {
+            tracing::info!(
+                "Update conflict: id={}, expected version {:?}, current {}",
+                before.id, input.expected_version, before.version
+            );
+            return Err(AppError::Conflict(Box::new(before)));
+        }
+
+        tracing::debug!("Todo {} updated successfully", input.todo_id);
+
+        let todo = target.first::<Todo>(conn)?;
+        JournalService::record(conn, OP_UPDATE, Some(&before), &todo)?;
+        audit.record(conn, OP_UPDATE, Some(&before), &todo)?;
+
+        let spawned = if todo.status == "completed" && before.status != "completed" {
+            RecurrenceService::on_completed(conn, &todo)?
+        } else {
+            None
+        };
+        Ok((todo, spawned))
+    }
+
+    /// 在事务内软删除一行，行不存在或已在回收站时返回 `None`
+    pub(crate) fn apply_delete(
+        conn: &mut SqliteConnection,
+        todo_id: &str,
+        now: &str,
+        audit: &AuditWriter,
+    ) -> AppResult<Option<Todo>> {
+        let target = todos::table.find(todo_id).filter(todos::deleted_at.is_null());
+        let Some(before) = target.first::<Todo>(conn).optional()? else {
+            return Ok(None);
+        };
+
+        let update_todo = UpdateTodo {
+            title: None,
+            status: None,
+            broker: None,
+            updated_at: now.to_string(),
+            conclusion: None,
+            deleted_at: Some(now.to_string()),
+            custom_fields: None,
+        };
+        diesel::update(target)
+            .set((&update_todo, todos::version.eq(todos::version + 1)))
+            .execute(conn)?;
+
+        let after = todos::table.find(todo_id).first::<Todo>(conn)?;
+        JournalService::record(conn, OP_DELETE, Some(&before), &after)?;
+        audit.record(conn, OP_DELETE, Some(&before), &after)?;
+        Ok(Some(after))
+    }
+
+    /// 去重并校验批量操作的 id 列表
+    fn bulk_ids(todo_ids: Vec<String>) -> AppResult<Vec<String>> {
+        if todo_ids.len() > MAX_BULK_SIZE {
+            return Err(AppError::Validation(format!("单次批量操作最多 {} 个任务", MAX_BULK_SIZE)));
+        }
+        let mut seen = HashSet::new();
+        Ok(todo_ids.into_iter().filter(|id| seen.insert(id.clone())).collect())
+    }
+
+    /// 更新 Todo
+    pub fn update(storage: &impl Storage, input: UpdateTodoInput) -> AppResult<Todo> {
+        tracing::debug!("TodoService::update - todo_id: {}", input.todo_id);
+        tracing::trace!(
+            "Update details - title: {:?}, status: {:?}, broker: {:?}",
+            input.title, input.status, input.broker
+        );
+
+        // 验证输入（如果提供）
+        Self::validate_update(&input)?;
+
+        let mut conn = storage.connection()?;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let (todo, spawned) = conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            Self::apply_update(conn, &input, &now, &audit)
+        })?;
+
+        tracing::info!("Updated todo: id={}", todo.id);
+        let mut changes = TodosChanged::updated(todo.clone());
+        changes.created.extend(spawned);
+        storage.todos_changed(&changes);
+        Ok(todo)
+    }
+
+    /// 批量更新 Todo
+    ///
+    /// 所有行在一个事务内使用同一个 `updated_at`；每行一个保存点，
+    /// 单行失败（如检查项未完成）只回滚该行，其余照常提交。
+    pub fn bulk_update(storage: &impl Storage, input: BulkUpdateTodosInput) -> AppResult<Vec<BulkItemResult>> {
+        let ids = Self::bulk_ids(input.todo_ids)?;
+        tracing::debug!("TodoService::bulk_update - {} todos", ids.len());
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let template = UpdateTodoInput {
+            todo_id: String::new(),
+            title: input.patch.title,
+            status: input.patch.status,
+            broker: input.patch.broker,
+            conclusion: input.patch.conclusion,
+            custom_fields: None,
+            expected_version: None,
+        };
+        Self::validate_update(&template)?;
+
+        let mut conn = storage.connection()?;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut changes = TodosChanged::default();
+
+        let results = conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            let mut results = Vec::with_capacity(ids.len());
+            for id in ids {
+                let row = UpdateTodoInput { todo_id: id.clone(), ..template.clone() };
+                match conn.transaction::<_, AppError, _>(|conn| Self::apply_update(conn, &row, &now, &audit)) {
+                    Ok((todo, spawned)) => {
+                        changes.updated.push(todo.clone());
+                        changes.created.extend(spawned);
+                        results.push(BulkItemResult::ok(todo));
+                    }
+                    Err(e) => {
+                        tracing::info!("Bulk update skipped todo {}: {}", id, e);
+                        results.push(BulkItemResult::failed(id, &e));
+                    }
+                }
+            }
+            Ok(results)
+        })?;
+
+        tracing::info!("Bulk updated {}/{} todos", changes.updated.len(), results.len());
+        if !changes.is_empty() {
+            storage.todos_changed(&changes);
+        }
+        Ok(results)
+    }
+
+    /// 删除 Todo，任务不存在或已在回收站时返回 NotFound
+    pub fn delete(storage: &impl Storage, input: DeleteTodoInput) -> AppResult<()> {
+        tracing::debug!("TodoService::delete - todo_id: {}", input.todo_id);
+        let mut conn = storage.connection()?;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            Self::apply_delete(conn, &input.todo_id, &now, &audit)?
+                .ok_or(AppError::Database(diesel::result::Error::NotFound))
+        })?;
+
+        tracing::info!("Deleted todo: id={}", input.todo_id);
+        storage.todos_changed(&TodosChanged::deleted(input.todo_id));
+        Ok(())
+    }
+
+    /// 批量删除 Todo（移入回收站），每行一个保存点，返回逐个结果
+    pub fn bulk_delete(storage: &impl Storage, input: BulkDeleteTodosInput) -> AppResult<Vec<BulkItemResult>> {
+        let ids = Self::bulk_ids(input.todo_ids)?;
+        tracing::debug!("TodoService::bulk_delete - {} todos", ids.len());
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = storage.connection()?;
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut changes = TodosChanged::default();
+
+        let results = conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, storage.change_source())?;
+            let mut results = Vec::with_capacity(ids.len());
+            for id in ids {
+                let deleted = conn.transaction::<_, AppError, _>(|conn| {
+                    Self::apply_delete(conn, &id, &now, &audit)?
+                        .ok_or(AppError::Database(diesel::result::Error::NotFound))
+                });
+                match deleted {
+                    Ok(todo) => {
+                        changes.deleted.push(todo.id.clone());
+                        results.push(BulkItemResult::ok(todo));
+                    }
+                    Err(e) => {
+                        tracing::info!("Bulk delete skipped todo {}: {}", id, e);
+                        results.push(BulkItemResult::failed(id, &e));
+                    }
+                }
+            }
+            Ok(results)
+        })?;
+
+        tracing::info!("Bulk deleted {}/{} todos", changes.deleted.len(), results.len());
+        if !changes.is_empty() {
+            storage.todos_changed(&changes);
+        }
+        Ok(results)
+    }
+
+    /// 获取回收站中的 Todos（已软删除）
+    pub fn get_deleted(storage: &impl Storage) -> AppResult<Vec<Todo>> {
+        tracing::debug!("TodoService::get_deleted");
+        let mut conn = storage.connection()?;
+
+        let todos_list = todos::table
+            .filter(todos::deleted_at.is_not_null())
+            .order(todos::deleted_at.desc())
+            .load::<Todo>(&mut conn)?;
+        tracing::debug!("Retrieved {} deleted todos", todos_list.len());
+        Ok(todos_list)
+    }
+
+    /// 清空回收站：在一个事务内物理删除软删除的任务及其同步绑定、检查项、关联、标签与撤销记录
+    ///
+    /// 未配置同步时全部删除；配置过同步时，删除尚未推送到服务端的任务会保留并在结果中返回，
+    /// 否则其他设备收不到这次删除。
+    pub fn empty_trash(storage: &impl Storage) -> AppResult<EmptyTrashReport> {
+        let mut conn = storage.connection()?;
+        let report = conn.transaction::<_, AppError, _>(|conn| {
+            let deleted = todos::table
+                .filter(todos::deleted_at.is_not_null())
+                .select((todos::id, todos::updated_at))
+                .load::<(String, String)>(conn)?;
+            let bindings = todo_sync_profiles::table
+                .filter(todo_sync_profiles::todo_id.eq_any(
+                    todos::table.filter(todos::deleted_at.is_not_null()).select(todos::id),
+                ))
+                .load::<(String, String)>(conn)?
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+            let cursors = SyncService::synced_cursors(conn)?
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+            let sync_configured = !cursors.is_empty() || !bindings.is_empty();
+
+            let mut report = EmptyTrashReport::default();
+            for (id, updated_at) in deleted {
+                let pushed = bindings
+                    .get(&id)
+                    .and_then(|profile| cursors.get(profile))
+                    .is_some_and(|synced_until| updated_at <= *synced_until);
+                if !sync_configured || pushed {
+                    report.purged_ids.push(id);
+                } else {
+                    report.kept_ids.push(id);
+                }
+            }
+            SyncService::delete_rows(conn, &report.purged_ids)?;
+            Ok(report)
+        })?;
+
+        if !report.kept_ids.is_empty() {
+            tracing::warn!(
+                "Kept {} deleted todos whose deletion has not been synced yet",
+                report.kept_ids.len()
+            );
+        }
+        tracing::info!("Emptied trash: purged {} todos", report.purged_ids.len());
+        Ok(report)
+    }
+
+    /// 从回收站恢复 Todo
+    pub fn restore(storage: &impl Storage, input: RestoreTodoInput) -> AppResult<Todo> {
+        tracing::debug!("TodoService::restore - todo_id: {}", input.todo_id);
+        let mut conn = storage.connection()?;
+
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let restored_by = input
+            .restored_by
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(current_actor);
+
+        let todo = conn.transaction::<_, AppError, _>(|conn| {
+            let target = todos::table
+                .find(&input.todo_id)
+                .filter(todos::deleted_at.is_not_null());
+            let Some(before) = target.first::<Todo>(conn).optional()? else {
+                return Err(AppError::Validation("回收站中不存在该任务".to_string()));
+            };
+
+            diesel::update(target)
+                .set((
+                    todos::deleted_at.eq(None::<String>),
+                    todos::updated_at.eq(&now),
+                    todos::restored_at.eq(Some(&now)),
+                    todos::restored_by.eq(Some(&restored_by)),
+                    todos::version.eq(todos::version + 1),
+                ))
+                .execute(conn)?;
+
+            let todo = todos::table
+                .find(&input.todo_id)
+                .first::<Todo>(conn)?;
+            AuditWriter::new(conn, storage.change_source())?.record(conn, OP_RESTORE, Some(&before), &todo)?;
+            Ok(todo)
+        })?;
+
+        tracing::info!("Restored todo: id={}, by={}", todo.id, restored_by);
+        storage.todos_changed(&TodosChanged::updated(todo.clone()));
+        Ok(todo)
+    }
+
+    /// 搜索 Todos
+    pub fn search(storage: &impl Storage, input: SearchTodoInput) -> AppResult<Vec<Todo>> {
+        tracing::debug!("TodoService::search - query: {}", input.query);
+        let mut conn = storage.connection()?;
+
+        // 转义 LIKE 特殊字符，防止 SQL 注入
+        let escaped_query = escape_like_pattern(&input.query);
+        let search_pattern = format!("%{}%", escaped_query);
+
+        let mut query = todos::table
+            .filter(
+                todos::title.like(&search_pattern)
+                    .or(todos::broker.like(&search_pattern))
+            )
+            .filter(todos::deleted_at.is_null())
+            .into_boxed();
+        if !input.tag_ids.is_empty() {
+            let tagged = todo_tags::table
+                .filter(todo_tags::tag_id.eq_any(&input.tag_ids))
+                .select(todo_tags::todo_id);
+            query = query.filter(todos::id.eq_any(tagged));
+        }
+        if let Some(filter) = &input.field_filter {
+            query = CustomFieldService::filter_todos(query, filter)?;
+        }
+        let todos_list = query.load::<Todo>(&mut conn)?;
+
+        tracing::debug!("Search returned {} results", todos_list.len());
+        Ok(todos_list)
+    }
+
+    /// 获取增量变更（包含已删除）
+    pub fn get_updated_after(storage: &impl Storage, updated_after: Option<String>) -> AppResult<Vec<Todo>> {
+        let mut conn = storage.connection()?;
+        let mut query = todos::table.into_boxed();
+
+        if let Some(after) = updated_after {
+            query = query.filter(todos::updated_at.gt(after));
+        }
+
+        let results = query.order(todos::updated_at.asc()).load::<Todo>(&mut conn)?;
+        Ok(results)
+    }
+
+    /// 同步字段与本地行完全相同（版本号与检查项进度只在本地维护，不比较）
+    fn same_content(local: &Todo, remote: &Todo) -> bool {
+        local.title == remote.title
+            && local.status == remote.status
+            && local.broker == remote.broker
+            && local.created_at == remote.created_at
+            && local.updated_at == remote.updated_at
+            && local.conclusion == remote.conclusion
+            && local.deleted_at == remote.deleted_at
+            && local.restored_at == remote.restored_at
+            && local.restored_by == remote.restored_by
+            && local.series_id == remote.series_id
+            && local.due_date == remote.due_date
+            && local.custom_fields == remote.custom_fields
+    }
+
+    /// 批量 upsert，用于同步
+    ///
+    /// 整批在一个事务内执行，按 `UPSERT_CHUNK_SIZE` 分块做多行插入；
+    /// 远端行总是覆盖本地行，与本地完全相同的行跳过（不递增版本、不记审计）。
+    /// 实际写入的行按 `source` 记入审计日志。
+    pub fn upsert_batch(storage: &impl Storage, items: Vec<Todo>, source: ChangeSource) -> AppResult<UpsertSummary> {
+        let mut summary = UpsertSummary::default();
+        if items.is_empty() {
+            return Ok(summary);
+        }
+
+        tracing::debug!("TodoService::upsert_batch - {} items", items.len());
+        let mut conn = storage.connection()?;
+        let mut changes = TodosChanged::default();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let audit = AuditWriter::new(conn, source)?;
+            for chunk in items.chunks(UPSERT_CHUNK_SIZE) {
+                let ids: Vec<&str> = chunk.iter().map(|t| t.id.as_str()).collect();
+                let existing: HashMap<String, Todo> = todos::table
+                    .filter(todos::id.eq_any(ids))
+                    .load::<Todo>(conn)?
+                    .into_iter()
+                    .map(|todo| (todo.id.clone(), todo))
+                    .collect();
+
+                let mut values = Vec::with_capacity(chunk.len());
+                for todo in chunk {
+                    // 重复系列只在本地，远端行沿用本地的 series_id（新行没有系列）
+                    let mut todo = todo.clone();
+                    todo.series_id = existing.get(&todo.id).and_then(|local| local.series_id.clone());
+                    let is_new = match existing.get(&todo.id) {
+                        Some(local) if Self::same_content(local, &todo) => {
+                            summary.skipped += 1;
+                            continue;
+                        }
+                        Some(_) => {
+                            summary.updated += 1;
+                            false
+                        }
+                        None => {
+                            summary.inserted += 1;
+                            true
+                        }
+                    };
+                    if todo.deleted_at.is_some() {
+                        changes.deleted.push(todo.id.clone());
+                    } else if is_new {
+                        changes.created.push(todo.clone());
+                    } else {
+                        changes.updated.push(todo.clone());
+                    }
+                    values.push(NewTodo::from(todo));
+                }
+
+                if values.is_empty() {
+                    continue;
+                }
+
+                diesel::insert_into(todos::table)
+                    .values(&values)
+                    .on_conflict(todos::id)
+                    .do_update()
+                    .set((
+                        todos::title.eq(excluded(todos::title)),
+                        todos::status.eq(excluded(todos::status)),
+                        todos::broker.eq(excluded(todos::broker)),
+                        todos::created_at.eq(excluded(todos::created_at)),
+                        todos::updated_at.eq(excluded(todos::updated_at)),
+                        todos::conclusion.eq(excluded(todos::conclusion)),
+                        todos::deleted_at.eq(excluded(todos::deleted_at)),
+                        todos::restored_at.eq(excluded(todos::restored_at)),
+                        todos::restored_by.eq(excluded(todos::restored_by)),
+                        todos::series_id.eq(excluded(todos::series_id)),
+                        todos::due_date.eq(excluded(todos::due_date)),
+                        todos::custom_fields.eq(excluded(todos::custom_fields)),
+                        // 远端覆盖也算一次本地写入，让持有旧版本的编辑产生冲突
+                        todos::version.eq(todos::version + 1),
+                    ))
+                    .execute(conn)?;
+
+                let applied: Vec<&str> = values.iter().map(|t| t.id.as_str()).collect();
+                let entries = todos::table
+                    .filter(todos::id.eq_any(applied))
+                    .load::<Todo>(conn)?
+                    .iter()
+                    .map(|after| {
+                        let before = existing.get(&after.id);
+                        let operation = match before {
+                            None => OP_CREATE,
+                            Some(before) if before.deleted_at.is_none() && after.deleted_at.is_some() => OP_DELETE,
+                            Some(_) => OP_UPDATE,
+                        };
+                        audit.entry(operation, before, after)
+                    })
+                    .collect::<AppResult<Vec<_>>>()?;
+                audit.insert(conn, entries)?;
+            }
+            Ok(())
+        })?;
+
+        tracing::info!(
+            "Upsert batch applied: inserted={}, updated={}, skipped={}",
+            summary.inserted, summary.updated, summary.skipped
+        );
+        if !changes.is_empty() {
+            storage.todos_changed(&changes);
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::TodoPatch;
+    use crate::test_support::{create_todo, memory_pool, remote_todo};
+
+    const ID: &str = "00000000-0000-4000-8000-000000000001";
+
+    #[test]
+    fn upsert_batch_inserts_then_overwrites_same_timestamp() {
+        let pool = memory_pool();
+        let first = remote_todo(ID, "原标题", "2026-01-02T00:00:00Z");
+        let summary = TodoService::upsert_batch(&pool, vec![first], ChangeSource::Sync).unwrap();
+        assert_eq!((summary.inserted, summary.updated, summary.skipped), (1, 0, 0));
+
+        // 时间戳相同但内容不同：远端仍然覆盖本地
+        let second = remote_todo(ID, "新标题", "2026-01-02T00:00:00Z");
+        let summary = TodoService::upsert_batch(&pool, vec![second], ChangeSource::Sync).unwrap();
+        assert_eq!((summary.inserted, summary.updated, summary.skipped), (0, 1, 0));
+
+        let mut conn = pool.connection().unwrap();
+        let stored = todos::table.find(ID).first::<Todo>(&mut conn).unwrap();
+        assert_eq!(stored.title, "新标题");
+        assert_eq!(stored.version, 2);
+    }
+
+    #[test]
+    fn upsert_batch_skips_identical_rows() {
+        let pool = memory_pool();
+        let todo = remote_todo(ID, "标题", "2026-01-02T00:00:00Z");
+        TodoService::upsert_batch(&pool, vec![todo.clone()], ChangeSource::Sync).unwrap();
+
+        let summary = TodoService::upsert_batch(&pool, vec![todo], ChangeSource::Sync).unwrap();
+        assert_eq!((summary.inserted, summary.updated, summary.skipped), (0, 0, 1));
+
+        let mut conn = pool.connection().unwrap();
+        let stored = todos::table.find(ID).first::<Todo>(&mut conn).unwrap();
+        assert_eq!(stored.version, 1);
+    }
+
+    fn rename(todo_id: &str, title: &str, expected_version: i32) -> UpdateTodoInput {
+        UpdateTodoInput {
+            todo_id: todo_id.to_string(),
+            title: Some(title.to_string()),
+            status: None,
+            broker: None,
+            conclusion: None,
+            custom_fields: None,
+            expected_version: Some(expected_version),
+        }
+    }
+
+    #[test]
+    fn stale_version_update_returns_conflict_with_current_row() {
+        let pool = memory_pool();
+        let todo = create_todo(&pool, "原标题");
+        TodoService::update(&pool, rename(&todo.id, "第一次修改", todo.version)).unwrap();
+
+        let err = TodoService::update(&pool, rename(&todo.id, "过期的修改", todo.version)).unwrap_err();
+        let AppError::Conflict(ref current) = err else {
+            panic!("expected conflict, got {:?}", err);
+        };
+        assert_eq!(current.title, "第一次修改");
+        assert_eq!(current.version, todo.version + 1);
+
+        let payload = serde_json::to_value(&err).unwrap();
+        assert_eq!(payload["kind"], "conflict");
+        assert_eq!(payload["current"]["title"], "第一次修改");
+    }
+
+    #[test]
+    fn completing_requires_a_conclusion_in_the_input() {
+        let pool = memory_pool();
+        let todo = create_todo(&pool, "任务");
+        let with_conclusion = UpdateTodoInput {
+            conclusion: Some("旧结论".to_string()),
+            ..rename(&todo.id, "任务", todo.version)
+        };
+        TodoService::update(&pool, with_conclusion).unwrap();
+
+        // 已有结论不能代替本次输入
+        let complete = UpdateTodoInput {
+            status: Some("completed".to_string()),
+            ..rename(&todo.id, "任务", todo.version + 1)
+        };
+        assert!(matches!(TodoService::update(&pool, complete.clone()), Err(AppError::Validation(_))));
+
+        let patch = TodoPatch { title: None, status: Some("completed".to_string()), broker: None, conclusion: None };
+        let bulk = BulkUpdateTodosInput { todo_ids: vec![todo.id.clone()], patch };
+        assert!(matches!(TodoService::bulk_update(&pool, bulk), Err(AppError::Validation(_))));
+
+        let complete = UpdateTodoInput { conclusion: Some("新结论".to_string()), ..complete };
+        assert_eq!(TodoService::update(&pool, complete).unwrap().status, "completed");
+    }
+
+    #[test]
+    fn missing_and_trashed_todos_are_reported_as_not_found() {
+        let pool = memory_pool();
+        let live = create_todo(&pool, "保留");
+        let gone = create_todo(&pool, "已删除");
+        TodoService::delete(&pool, DeleteTodoInput { todo_id: gone.id.clone() }).unwrap();
+        assert!(matches!(
+            TodoService::delete(&pool, DeleteTodoInput { todo_id: gone.id.clone() }),
+            Err(AppError::Database(diesel::result::Error::NotFound))
+        ));
+
+        let ids = vec![live.id.clone(), gone.id.clone(), "missing".to_string()];
+        let errors = |results: Vec<BulkItemResult>| results.into_iter().map(|r| r.error).collect::<Vec<_>>();
+        let not_found = Some("任务不存在".to_string());
+
+        let patch = TodoPatch { title: Some("改名".to_string()), status: None, broker: None, conclusion: None };
+        let updated = TodoService::bulk_update(&pool, BulkUpdateTodosInput { todo_ids: ids.clone(), patch }).unwrap();
+        assert_eq!(errors(updated), vec![None, not_found.clone(), not_found.clone()]);
+
+        let deleted = TodoService::bulk_delete(&pool, BulkDeleteTodosInput { todo_ids: ids }).unwrap();
+        assert_eq!(errors(deleted), vec![None, not_found.clone(), not_found]);
+    }
+
+    fn trashed(id: &str) -> Todo {
+        let mut todo = remote_todo(id, "已删除", "2026-01-02T00:00:00Z");
+        todo.deleted_at = Some("2026-01-02T00:00:00Z".to_string());
+        todo
+    }
+
+    #[test]
+    fn empty_trash_deletes_everything_without_sync() {
+        let pool = memory_pool();
+        let rows = vec![trashed(ID), remote_todo("live", "保留", "2026-01-02T00:00:00Z")];
+        TodoService::upsert_batch(&pool, rows, ChangeSource::Sync).unwrap();
+
+        let report = TodoService::empty_trash(&pool).unwrap();
+        assert_eq!(report.purged_ids, vec![ID.to_string()]);
+        assert!(report.kept_ids.is_empty());
+
+        let mut conn = pool.connection().unwrap();
+        let left = todos::table.select(todos::id).load::<String>(&mut conn).unwrap();
+        assert_eq!(left, vec!["live".to_string()]);
+    }
+
+    #[test]
+    fn empty_trash_keeps_unsynced_deletions_when_sync_is_configured() {
+        let pool = memory_pool();
+        let mut newer = trashed("unsynced");
+        newer.updated_at = "2026-01-05T00:00:00Z".to_string();
+        TodoService::upsert_batch(&pool, vec![trashed(ID), newer], ChangeSource::Sync).unwrap();
+        let ids = vec![ID.to_string(), "unsynced".to_string()];
+        SyncService::bind(&pool, "default", &ids).unwrap();
+        let mut conn = pool.connection().unwrap();
+        SyncService::set_meta(&mut conn, "synced_until:default", "2026-01-03T00:00:00Z").unwrap();
+        drop(conn);
+
+        let report = TodoService::empty_trash(&pool).unwrap();
+        assert_eq!(report.purged_ids, vec![ID.to_string()]);
+        assert_eq!(report.kept_ids, vec!["unsynced".to_string()]);
+    }
+
+    #[test]
+    fn upsert_batch_applies_older_remote_rows() {
+        let pool = memory_pool();
+        let newer = remote_todo(ID, "本地较新", "2026-01-03T00:00:00Z");
+        TodoService::upsert_batch(&pool, vec![newer], ChangeSource::Sync).unwrap();
+
+        let older = remote_todo(ID, "远端较旧", "2026-01-02T00:00:00Z");
+        let summary = TodoService::upsert_batch(&pool, vec![older], ChangeSource::Sync).unwrap();
+        assert_eq!(summary.updated, 1);
+
+        let mut conn = pool.connection().unwrap();
+        let stored = todos::table.find(ID).first::<Todo>(&mut conn).unwrap();
+        assert_eq!(stored.title, "远端较旧");
+    }
+}
@@ -0,0 +1,38 @@
+use diesel::r2d2::{ConnectionManager, PooledConnection};
+use diesel::sqlite::SqliteConnection;
+use diesel_migrations::MigrationHarness;
+
+use crate::db::{get_connection, DbPool};
+use crate::dto::{ChangeSource, TodosChanged};
+use crate::utils::{AppError, AppResult};
+use crate::MIGRATIONS;
+
+pub type DbConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+/// 存储抽象：业务服务只通过它获取数据库连接
+pub trait Storage {
+    /// 获取一个数据库连接
+    fn connection(&self) -> AppResult<DbConnection>;
+
+    /// 写操作的来源，记录在审计日志中
+    fn change_source(&self) -> ChangeSource {
+        ChangeSource::Ui
+    }
+
+    /// 任务写操作提交后调用，默认不做处理；桌面端借此通知各窗口
+    fn todos_changed(&self, _changes: &TodosChanged) {}
+
+    /// 执行尚未应用的迁移
+    fn run_migrations(&self) -> AppResult<()> {
+        let mut conn = self.connection()?;
+        conn.run_pending_migrations(MIGRATIONS)
+            .map_err(|e| AppError::Migration(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl Storage for DbPool {
+    fn connection(&self) -> AppResult<DbConnection> {
+        get_connection(self)
+    }
+}
@@ -0,0 +1,53 @@
+//! 单元测试共用：内存数据库与测试数据
+
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+
+use crate::db::{DbPool, Todo};
+use crate::dto::CreateTodoInput;
+use crate::services::TodoService;
+use crate::storage::Storage;
+
+/// 已执行迁移的内存数据库（单连接，保证各次获取的是同一个库）
+pub fn memory_pool() -> DbPool {
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+        .expect("Failed to create memory pool");
+    pool.run_migrations().expect("Failed to run migrations");
+    pool
+}
+
+/// 一条远端任务行
+pub fn remote_todo(id: &str, title: &str, updated_at: &str) -> Todo {
+    Todo {
+        id: id.to_string(),
+        title: title.to_string(),
+        status: "pending".to_string(),
+        broker: "测试券商".to_string(),
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        updated_at: updated_at.to_string(),
+        conclusion: None,
+        deleted_at: None,
+        restored_at: None,
+        restored_by: None,
+        version: 1,
+        series_id: None,
+        due_date: None,
+        items_total: 0,
+        items_done: 0,
+        custom_fields: "{}".to_string(),
+    }
+}
+
+/// 通过服务创建一条待处理任务
+pub fn create_todo(pool: &DbPool, title: &str) -> Todo {
+    let input = CreateTodoInput {
+        title: title.to_string(),
+        status: "pending".to_string(),
+        broker: "测试券商".to_string(),
+        conclusion: None,
+        custom_fields: None,
+    };
+    TodoService::create(pool, input).expect("Failed to create todo")
+}
@@ -0,0 +1,59 @@
+use thiserror::Error;
+
+use crate::db::Todo;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("Database error: {0}")]
+    Database(#[from] diesel::result::Error),
+
+    #[error("Connection pool error: {0}")]
+    PoolError(String),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("Migration error: {0}")]
+    Migration(String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// 乐观并发冲突，携带当前行（序列化为 `{ kind: "conflict", message, current }`，前端据此提供合并）
+    #[error("Conflict: 任务已被其他写入修改")]
+    Conflict(Box<Todo>),
+}
+
+/// 冲突错误的结构化形式
+#[derive(serde::Serialize)]
+struct ConflictPayload<'a> {
+    kind: &'static str,
+    message: String,
+    current: &'a Todo,
+}
+
+/// 冲突错误序列化为对象，其余错误序列化为消息字符串
+impl serde::Serialize for AppError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            AppError::Conflict(current) => ConflictPayload {
+                kind: "conflict",
+                message: self.to_string(),
+                current,
+            }
+            .serialize(serializer),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,7 @@
+pub mod actor;
+pub mod error;
+pub mod validation;
+
+pub use actor::current_actor;
+pub use error::{AppError, AppResult};
+pub use validation::{escape_like_pattern, TodoInput};
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use sqd_core::dto::{HealthResponse, LoginRequest, RefreshRequest, TokenResponse};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+
+/// access token 有效期
+const ACCESS_TOKEN_TTL: Duration = Duration::from_secs(60 * 60);
+/// refresh token 有效期
+const REFRESH_TOKEN_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// 用户不存在时用来校验的固定哈希（默认参数），让未知用户与密码错误耗时相同
+const DUMMY_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$+JFVnrIET12xsWTe1zyP+Q$ZDKpL7rxKU9ZJgTdeY+8uOwPSWEswGn7je5YwsE8UuE";
+
+#[derive(Debug)]
+struct Session {
+    username: String,
+    expires_at: Instant,
+    /// 同一次登录 / 刷新签发的另一个令牌，注销时一起作废
+    pair: String,
+}
+
+#[derive(Debug, Default)]
+struct Sessions {
+    access: HashMap<String, Session>,
+    refresh: HashMap<String, Session>,
+}
+
+/// 对密码做 argon2 哈希，返回 PHC 字符串（`$argon2id$...`）
+pub fn hash_password(password: &str) -> String {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes()).expect("Invalid salt length");
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("Failed to hash password")
+        .to_string()
+}
+
+/// 账号与会话状态（会话仅保存在内存，服务重启后需重新登录）
+#[derive(Debug, Default)]
+pub struct AuthState {
+    /// 用户名 → 密码的 argon2 PHC 字符串
+    users: HashMap<String, String>,
+    sessions: Mutex<Sessions>,
+}
+
+impl AuthState {
+    /// 解析 `user:password,user2:password2` 格式的账号列表
+    ///
+    /// 密码可以直接写 argon2 PHC 字符串（`$argon2id$...`，见 `hash_password`），
+    /// 明文密码在启动时哈希，内存中不保留明文。
+    pub fn from_users_spec(spec: &str) -> Self {
+        // PHC 字符串的参数段含逗号（`m=19456,t=2,p=1`），不含冒号的片段接回上一项
+        let mut entries: Vec<String> = Vec::new();
+        for segment in spec.split(',') {
+            match entries.last_mut() {
+                Some(last) if !segment.contains(':') => {
+                    last.push(',');
+                    last.push_str(segment);
+                }
+                _ => entries.push(segment.to_string()),
+            }
+        }
+        let users = entries
+            .iter()
+            .filter_map(|pair| pair.split_once(':'))
+            .map(|(user, password)| (user.trim().to_string(), password))
+            .filter(|(user, password)| !user.is_empty() && !password.is_empty())
+            .map(|(user, password)| {
+                let hash = match PasswordHash::new(password) {
+                    Ok(_) => password.to_string(),
+                    Err(_) => hash_password(password),
+                };
+                (user, hash)
+            })
+            .collect();
+        Self {
+            users,
+            sessions: Mutex::new(Sessions::default()),
+        }
+    }
+
+    /// 校验密码（argon2 校验为常数时间比较；用户不存在时也校验一次固定哈希，不泄露用户名是否存在）
+    fn verify(&self, username: &str, password: &str) -> bool {
+        let (hash, known) = match self.users.get(username) {
+            Some(hash) => (hash.as_str(), true),
+            None => (DUMMY_HASH, false),
+        };
+        let matched = PasswordHash::new(hash)
+            .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+            .unwrap_or(false);
+        known && matched
+    }
+
+    /// 未配置任何账号时不校验令牌
+    pub fn enabled(&self) -> bool {
+        !self.users.is_empty()
+    }
+
+    fn issue(&self, username: &str) -> TokenResponse {
+        let access_token = Uuid::new_v4().simple().to_string();
+        let refresh_token = Uuid::new_v4().simple().to_string();
+        let now = Instant::now();
+
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.access.retain(|_, s| s.expires_at > now);
+        sessions.refresh.retain(|_, s| s.expires_at > now);
+        sessions.access.insert(
+            access_token.clone(),
+            Session {
+                username: username.to_string(),
+                expires_at: now + ACCESS_TOKEN_TTL,
+                pair: refresh_token.clone(),
+            },
+        );
+        sessions.refresh.insert(
+            refresh_token.clone(),
+            Session {
+                username: username.to_string(),
+                expires_at: now + REFRESH_TOKEN_TTL,
+                pair: access_token.clone(),
+            },
+        );
+
+        TokenResponse {
+            access_token,
+            refresh_token,
+            expires_in: ACCESS_TOKEN_TTL.as_secs() as i64,
+        }
+    }
+
+    fn validate(&self, access_token: &str) -> bool {
+        let sessions = self.sessions.lock().unwrap();
+        sessions
+            .access
+            .get(access_token)
+            .map(|s| s.expires_at > Instant::now())
+            .unwrap_or(false)
+    }
+
+    /// 作废该 access token 及同时签发的 refresh token，同一用户的其他会话不受影响
+    fn revoke(&self, access_token: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some(session) = sessions.access.remove(access_token) {
+            sessions.refresh.remove(&session.pair);
+        }
+    }
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+}
+
+/// 校验 `Authorization: Bearer <token>` 的中间件
+pub async fn require_auth(
+    State(auth): State<Arc<AuthState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !auth.enabled() {
+        return next.run(request).await;
+    }
+
+    match bearer_token(&request) {
+        Some(token) if auth.validate(token) => next.run(request).await,
+        _ => (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
+    }
+}
+
+/// GET /health：公开接口，供客户端探测连通性
+pub async fn health(State(auth): State<Arc<AuthState>>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        auth_required: auth.enabled(),
+    })
+}
+
+/// POST /auth/login
+pub async fn login(
+    State(auth): State<Arc<AuthState>>,
+    Json(input): Json<LoginRequest>,
+) -> ApiResult<Json<TokenResponse>> {
+    let username = input.username.clone();
+    let verified = tokio::task::spawn_blocking({
+        let auth = auth.clone();
+        move || auth.verify(&input.username, &input.password)
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Worker task failed: {}", e)))?;
+
+    if verified {
+        tracing::info!("User logged in: {}", username);
+        Ok(Json(auth.issue(&username)))
+    } else {
+        tracing::warn!("Login failed: {}", username);
+        Err(ApiError::unauthorized("用户名或密码错误"))
+    }
+}
+
+/// POST /auth/refresh：refresh token 一次性使用，换取新的一对令牌
+pub async fn refresh(
+    State(auth): State<Arc<AuthState>>,
+    Json(input): Json<RefreshRequest>,
+) -> ApiResult<Json<TokenResponse>> {
+    let session = auth
+        .sessions
+        .lock()
+        .unwrap()
+        .refresh
+        .remove(&input.refresh_token);
+
+    match session {
+        Some(session) if session.expires_at > Instant::now() => {
+            Ok(Json(auth.issue(&session.username)))
+        }
+        _ => Err(ApiError::unauthorized("refresh token 无效或已过期")),
+    }
+}
+
+/// POST /auth/logout
+pub async fn logout(State(auth): State<Arc<AuthState>>, request: Request) -> StatusCode {
+    if let Some(token) = bearer_token(&request) {
+        auth.revoke(token);
+    }
+    StatusCode::NO_CONTENT
+}
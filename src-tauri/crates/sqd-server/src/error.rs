@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use sqd_core::utils::AppError;
+
+/// HTTP 错误响应：状态码 + 纯文本消息（前端 `client.ts` 直接读取 text）；
+/// 冲突返回 JSON `{ kind: "conflict", message, current }`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+    body: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+            body: None,
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            message: message.into(),
+            body: None,
+        }
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(err: AppError) -> Self {
+        let status = match &err {
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Database(diesel::result::Error::NotFound) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", err);
+        }
+        let body = match &err {
+            AppError::Conflict(_) => serde_json::to_value(&err).ok(),
+            _ => None,
+        };
+        Self {
+            status,
+            message: err.to_string(),
+            body,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self.body {
+            Some(body) => (self.status, Json(body)).into_response(),
+            None => (self.status, self.message).into_response(),
+        }
+    }
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
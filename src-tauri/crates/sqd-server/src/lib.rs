@@ -0,0 +1,85 @@
+//! SQD 工作助手自托管同步服务
+//!
+//! 实现 `src/api/todo.ts` 调用的 REST 接口：`/todos` 增删改查、`/brokers`
+//! 以及 `/sync`（`last_sync` / `changes` / `server_time`），业务逻辑与桌面端共用。
+//! 配置账号后，除 `/auth/*` 外的接口都需要 `Authorization: Bearer <token>`。
+
+pub mod auth;
+pub mod error;
+pub mod routes;
+
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+use axum::middleware;
+use axum::routing::{get, patch, post};
+use axum::Router;
+use sqd_core::db::{establish_connection, DbPool};
+use sqd_core::Storage;
+
+use auth::AuthState;
+
+/// 与 `DEFAULT_API_BASE` 的路径部分一致
+pub const API_PREFIX: &str = "/assistant/api";
+
+/// 打开数据库并执行迁移
+pub fn open_database(database_url: &str) -> DbPool {
+    let pool = establish_connection(database_url);
+    match pool.run_migrations() {
+        Ok(_) => tracing::info!("Migrations applied successfully"),
+        Err(e) => tracing::warn!("Migration warning: {}", e),
+    }
+    pool
+}
+
+/// 路由共享状态
+#[derive(Clone)]
+pub struct ServerState {
+    pub pool: DbPool,
+    pub auth: Arc<AuthState>,
+}
+
+impl FromRef<ServerState> for DbPool {
+    fn from_ref(state: &ServerState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<ServerState> for Arc<AuthState> {
+    fn from_ref(state: &ServerState) -> Self {
+        state.auth.clone()
+    }
+}
+
+/// 构建路由
+pub fn router(pool: DbPool, auth: AuthState) -> Router {
+    let auth = Arc::new(auth);
+    if !auth.enabled() {
+        tracing::warn!("No users configured, authentication is disabled");
+    }
+
+    let protected: Router<ServerState> = Router::new()
+        .route("/todos", get(routes::list_todos).post(routes::create_todo))
+        .route(
+            "/todos/:id",
+            patch(routes::update_todo).delete(routes::delete_todo),
+        )
+        .route("/todos/bulk-update", post(routes::bulk_update_todos))
+        .route("/todos/bulk-delete", post(routes::bulk_delete_todos))
+        .route("/brokers", get(routes::list_brokers))
+        .route("/sync", post(routes::sync))
+        .route_layer(middleware::from_fn_with_state(
+            auth.clone(),
+            auth::require_auth,
+        ));
+
+    let public: Router<ServerState> = Router::new()
+        .route("/health", get(auth::health))
+        .route("/auth/login", post(auth::login))
+        .route("/auth/refresh", post(auth::refresh))
+        .route("/auth/logout", post(auth::logout));
+
+    Router::new()
+        .nest(API_PREFIX, protected.merge(public))
+        .with_state(ServerState { pool, auth })
+}
@@ -0,0 +1,46 @@
+use sqd_server::auth::AuthState;
+use tracing_subscriber::EnvFilter;
+
+/// 监听地址，默认仅本机
+const DEFAULT_ADDR: &str = "127.0.0.1:1980";
+/// 数据库文件路径
+const DEFAULT_DATABASE: &str = "sqd-server.db";
+
+#[tokio::main]
+async fn main() {
+    // `sqd-server hash-password <密码>`：输出可写入 SQD_SERVER_USERS 的 argon2 哈希
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, password] = args.as_slice() {
+        if command == "hash-password" {
+            println!("{}", sqd_server::auth::hash_password(password));
+            return;
+        }
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let addr = std::env::var("SQD_SERVER_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    let database_url =
+        std::env::var("SQD_SERVER_DB").unwrap_or_else(|_| DEFAULT_DATABASE.to_string());
+
+    tracing::info!("Database path: {}", database_url);
+    let pool = sqd_server::open_database(&database_url);
+    let auth = AuthState::from_users_spec(&std::env::var("SQD_SERVER_USERS").unwrap_or_default());
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to bind {}: {}", addr, e));
+    tracing::info!("Listening on http://{}{}", addr, sqd_server::API_PREFIX);
+
+    axum::serve(listener, sqd_server::router(pool, auth))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+            tracing::info!("Shutting down");
+        })
+        .await
+        .expect("Server error");
+}
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{SecondsFormat, Utc};
+use serde::Deserialize;
+use sqd_core::db::{DbPool, Todo};
+use sqd_core::dto::{
+    BulkDeleteTodosInput, BulkItemResult, BulkUpdateTodosInput, ChangeSource, CreateTodoInput,
+    DeleteTodoInput, SearchTodoInput, SyncRequest, SyncResponse, UpdateTodoInput,
+};
+use sqd_core::services::{BrokerService, TodoService};
+use sqd_core::utils::{AppError, AppResult};
+
+use crate::error::{ApiError, ApiResult};
+
+/// 在阻塞线程池中执行 Diesel 调用
+pub(crate) async fn blocking<T, F>(f: F) -> ApiResult<T>
+where
+    F: FnOnce() -> AppResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ApiError::internal(format!("Worker task failed: {}", e)))?
+        .map_err(ApiError::from)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub query: Option<String>,
+}
+
+/// GET /todos[?query=]
+pub async fn list_todos(
+    State(pool): State<DbPool>,
+    Query(params): Query<ListQuery>,
+) -> ApiResult<Json<Vec<Todo>>> {
+    let todos = blocking(move || match params.query {
+        Some(query) if !query.trim().is_empty() => {
+            TodoService::search(&pool, SearchTodoInput { query, tag_ids: Vec::new(), field_filter: None })
+        }
+        _ => TodoService::get_all(&pool),
+    })
+    .await?;
+    Ok(Json(todos))
+}
+
+/// POST /todos
+pub async fn create_todo(
+    State(pool): State<DbPool>,
+    Json(input): Json<CreateTodoInput>,
+) -> ApiResult<(StatusCode, Json<Todo>)> {
+    let todo = blocking(move || TodoService::create(&pool, input)).await?;
+    Ok((StatusCode::CREATED, Json(todo)))
+}
+
+/// PATCH /todos/:id 的请求体（id 取自路径）
+#[derive(Debug, Deserialize)]
+pub struct UpdateTodoBody {
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub broker: Option<String>,
+    pub conclusion: Option<String>,
+    /// 要修改的自定义字段值，值为 null 时清除
+    pub custom_fields: Option<serde_json::Map<String, serde_json::Value>>,
+    /// 提供时版本不一致返回 409，响应体为 `{ "kind": "conflict", "message", "current": <当前行> }`
+    pub expected_version: Option<i32>,
+}
+
+/// PATCH /todos/:id
+pub async fn update_todo(
+    State(pool): State<DbPool>,
+    Path(id): Path<String>,
+    Json(body): Json<UpdateTodoBody>,
+) -> ApiResult<Json<Todo>> {
+    // 复用 DTO 的状态校验
+    let input: UpdateTodoInput = serde_json::from_value(serde_json::json!({
+        "todoId": id,
+        "title": body.title,
+        "status": body.status,
+        "broker": body.broker,
+        "conclusion": body.conclusion,
+        "customFields": body.custom_fields,
+        "expectedVersion": body.expected_version,
+    }))
+    .map_err(|e| ApiError::from(AppError::Validation(e.to_string())))?;
+
+    let todo = blocking(move || TodoService::update(&pool, input)).await?;
+    Ok(Json(todo))
+}
+
+/// POST /todos/bulk-update：逐个返回结果，单个任务失败不影响其他任务
+pub async fn bulk_update_todos(
+    State(pool): State<DbPool>,
+    Json(input): Json<BulkUpdateTodosInput>,
+) -> ApiResult<Json<Vec<BulkItemResult>>> {
+    let results = blocking(move || TodoService::bulk_update(&pool, input)).await?;
+    Ok(Json(results))
+}
+
+/// POST /todos/bulk-delete
+pub async fn bulk_delete_todos(
+    State(pool): State<DbPool>,
+    Json(input): Json<BulkDeleteTodosInput>,
+) -> ApiResult<Json<Vec<BulkItemResult>>> {
+    let results = blocking(move || TodoService::bulk_delete(&pool, input)).await?;
+    Ok(Json(results))
+}
+
+/// DELETE /todos/:id
+pub async fn delete_todo(
+    State(pool): State<DbPool>,
+    Path(id): Path<String>,
+) -> ApiResult<StatusCode> {
+    blocking(move || TodoService::delete(&pool, DeleteTodoInput { todo_id: id })).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /brokers
+pub async fn list_brokers(State(pool): State<DbPool>) -> ApiResult<Json<Vec<String>>> {
+    let brokers = blocking(move || BrokerService::get_pool(&pool)).await?;
+    Ok(Json(brokers))
+}
+
+/// POST /sync：应用客户端变更，返回 `last_sync` 之后的服务端变更
+pub async fn sync(
+    State(pool): State<DbPool>,
+    Json(request): Json<SyncRequest>,
+) -> ApiResult<Json<SyncResponse>> {
+    let response = blocking(move || {
+        // 先取服务端时间，保证下一次同步不会漏掉本次处理期间的变更
+        let server_time = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let pushed: HashSet<(String, String)> = request
+            .changes
+            .iter()
+            .map(|t| (t.id.clone(), t.updated_at.clone()))
+            .collect();
+        let summary = TodoService::upsert_batch(&pool, request.changes, ChangeSource::Sync)?;
+        tracing::info!(
+            "Sync push applied: inserted={}, updated={}, skipped={}",
+            summary.inserted, summary.updated, summary.skipped
+        );
+
+        // 不回显客户端刚推送的同版本记录
+        let changes = TodoService::get_updated_after(&pool, request.last_sync)?
+            .into_iter()
+            .filter(|t| !pushed.contains(&(t.id.clone(), t.updated_at.clone())))
+            .collect();
+
+        Ok(SyncResponse { server_time, changes })
+    })
+    .await?;
+    Ok(Json(response))
+}
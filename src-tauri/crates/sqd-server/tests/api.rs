@@ -0,0 +1,240 @@
+//! 在临时端口上启动完整路由，验证登录、同步与错误状态码
+
+use std::net::SocketAddr;
+
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use sqd_core::db::Todo;
+use sqd_core::dto::{SyncResponse, TokenResponse};
+use sqd_server::auth::AuthState;
+
+struct TestServer {
+    base: String,
+    client: Client,
+    db_path: std::path::PathBuf,
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.db_path);
+    }
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        Self::start_with_users("alice:secret").await
+    }
+
+    async fn start_with_users(users: &str) -> Self {
+        let db_path = std::env::temp_dir().join(format!("sqd_server_test_{}.db", uuid::Uuid::new_v4()));
+        let pool = sqd_server::open_database(db_path.to_str().unwrap());
+        let auth = AuthState::from_users_spec(users);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr: SocketAddr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, sqd_server::router(pool, auth)).await.unwrap();
+        });
+
+        TestServer {
+            base: format!("http://{}{}", addr, sqd_server::API_PREFIX),
+            client: Client::new(),
+            db_path,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    async fn login(&self, password: &str) -> reqwest::Response {
+        self.client
+            .post(self.url("/auth/login"))
+            .json(&json!({ "username": "alice", "password": password }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn token(&self) -> String {
+        let response = self.login("secret").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json::<TokenResponse>().await.unwrap().access_token
+    }
+}
+
+fn todo(id: &str, title: &str) -> Todo {
+    Todo {
+        id: id.to_string(),
+        title: title.to_string(),
+        status: "pending".to_string(),
+        broker: "测试券商".to_string(),
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        updated_at: "2026-01-01T00:00:00Z".to_string(),
+        conclusion: None,
+        deleted_at: None,
+        restored_at: None,
+        restored_by: None,
+        version: 1,
+        series_id: None,
+        due_date: None,
+        items_total: 0,
+        items_done: 0,
+        custom_fields: "{}".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn login_rejects_wrong_password() {
+    let server = TestServer::start().await;
+    assert_eq!(server.login("wrong").await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn login_rejects_unknown_user() {
+    let server = TestServer::start().await;
+    let response = server
+        .client
+        .post(server.url("/auth/login"))
+        .json(&json!({ "username": "mallory", "password": "secret" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn protected_routes_require_valid_token() {
+    let server = TestServer::start().await;
+    let response = server.client.get(server.url("/todos")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .client
+        .get(server.url("/todos"))
+        .bearer_auth("not-a-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let token = server.token().await;
+    let response = server.client.get(server.url("/todos")).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn sync_push_is_pulled_by_another_client() {
+    let server = TestServer::start().await;
+    let token = server.token().await;
+
+    let pushed = todo("00000000-0000-4000-8000-000000000001", "推送的任务");
+    let response = server
+        .client
+        .post(server.url("/sync"))
+        .bearer_auth(&token)
+        .json(&json!({ "last_sync": null, "changes": [pushed] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // 不回显刚推送的记录
+    assert!(response.json::<SyncResponse>().await.unwrap().changes.is_empty());
+
+    let response = server
+        .client
+        .post(server.url("/sync"))
+        .bearer_auth(&token)
+        .json(&json!({ "last_sync": null, "changes": [] }))
+        .send()
+        .await
+        .unwrap();
+    let pulled = response.json::<SyncResponse>().await.unwrap().changes;
+    assert_eq!(pulled.len(), 1);
+    assert_eq!(pulled[0].title, "推送的任务");
+}
+
+#[tokio::test]
+async fn stale_update_returns_conflict() {
+    let server = TestServer::start().await;
+    let token = server.token().await;
+
+    let created = server
+        .client
+        .post(server.url("/todos"))
+        .bearer_auth(&token)
+        .json(&json!({ "title": "原标题", "status": "pending", "broker": "测试券商" }))
+        .send()
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+    let path = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let response = server
+        .client
+        .patch(server.url(&path))
+        .bearer_auth(&token)
+        .json(&json!({ "title": "第一次修改", "expected_version": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = server
+        .client
+        .patch(server.url(&path))
+        .bearer_auth(&token)
+        .json(&json!({ "title": "过期的修改", "expected_version": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.json::<Value>().await.unwrap();
+    assert_eq!(body["kind"], "conflict");
+    assert_eq!(body["current"]["title"], "第一次修改");
+}
+
+#[tokio::test]
+async fn logout_revokes_only_the_presented_session() {
+    let server = TestServer::start().await;
+    let first = server.login("secret").await.json::<TokenResponse>().await.unwrap();
+    let second = server.login("secret").await.json::<TokenResponse>().await.unwrap();
+
+    let response = server
+        .client
+        .post(server.url("/auth/logout"))
+        .bearer_auth(&first.access_token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let refresh = |token: String| {
+        server
+            .client
+            .post(server.url("/auth/refresh"))
+            .json(&json!({ "refresh_token": token }))
+            .send()
+    };
+    assert_eq!(refresh(first.refresh_token).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(refresh(second.refresh_token).await.unwrap().status(), StatusCode::OK);
+
+    let response = server
+        .client
+        .get(server.url("/todos"))
+        .bearer_auth(&second.access_token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn users_spec_accepts_password_hashes() {
+    let spec = format!("alice:{},bob:plain", sqd_server::auth::hash_password("secret"));
+    let server = TestServer::start_with_users(&spec).await;
+    assert_eq!(server.login("secret").await.status(), StatusCode::OK);
+    assert_eq!(server.login("wrong").await.status(), StatusCode::UNAUTHORIZED);
+}
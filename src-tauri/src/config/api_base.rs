@@ -1,66 +1,197 @@
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 
+use super::paths::config_file_path;
 use crate::utils::{AppError, AppResult};
 
 pub const DEFAULT_API_BASE: &str = "http://47.108.156.226:1980/assistant/api";
+pub const DEFAULT_PROFILE: &str = "default";
 const CONFIG_FILE: &str = "assistant_api.json";
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ApiConfig {
+/// 单个服务器配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiProfile {
+    pub name: String,
+    pub api_base: String,
+    /// 自定义 CA 证书（PEM 文件路径），用于内网自签名服务器
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// SPKI 指纹（base64 编码的 SHA-256），配置后证书链中至少一个证书需匹配
+    #[serde(default)]
+    pub spki_pins: Vec<String>,
+    /// 是否允许非本机的明文 HTTP 地址
+    #[serde(default)]
+    pub allow_insecure_http: bool,
+}
+
+impl ApiProfile {
+    pub fn new(name: &str, api_base: &str) -> Self {
+        ApiProfile {
+            name: name.to_string(),
+            api_base: api_base.to_string(),
+            ca_bundle: None,
+            spki_pins: Vec::new(),
+            allow_insecure_http: false,
+        }
+    }
+}
+
+/// `assistant_api.json` 的内容：命名的服务器配置列表 + 当前配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    pub active_profile: String,
+    pub profiles: Vec<ApiProfile>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        ApiConfig {
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: vec![ApiProfile::new(DEFAULT_PROFILE, DEFAULT_API_BASE)],
+        }
+    }
+}
+
+impl ApiConfig {
+    /// 当前生效的服务器配置
+    pub fn active(&self) -> ApiProfile {
+        self.profiles
+            .iter()
+            .find(|p| p.name == self.active_profile)
+            .or_else(|| self.profiles.first())
+            .cloned()
+            .unwrap_or_else(|| ApiProfile::new(DEFAULT_PROFILE, DEFAULT_API_BASE))
+    }
+
+    fn active_mut(&mut self) -> &mut ApiProfile {
+        let name = self.active().name;
+        if !self.profiles.iter().any(|p| p.name == name) {
+            self.profiles.push(ApiProfile::new(&name, DEFAULT_API_BASE));
+        }
+        self.active_profile = name.clone();
+        self.profiles
+            .iter_mut()
+            .find(|p| p.name == name)
+            .expect("active profile exists")
+    }
+}
+
+/// 旧版单服务器格式（仅 `api_base` 等字段），读取时迁移为 `default` 配置
+#[derive(Debug, Deserialize)]
+struct LegacyApiConfig {
     api_base: String,
+    #[serde(default)]
+    ca_bundle: Option<String>,
+    #[serde(default)]
+    spki_pins: Vec<String>,
+    #[serde(default)]
+    allow_insecure_http: bool,
+}
+
+impl From<LegacyApiConfig> for ApiConfig {
+    fn from(legacy: LegacyApiConfig) -> Self {
+        ApiConfig {
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: vec![ApiProfile {
+                name: DEFAULT_PROFILE.to_string(),
+                api_base: legacy.api_base,
+                ca_bundle: legacy.ca_bundle,
+                spki_pins: legacy.spki_pins,
+                allow_insecure_http: legacy.allow_insecure_http,
+            }],
+        }
+    }
 }
 
 fn config_path(app: &AppHandle) -> AppResult<PathBuf> {
-    let dir = app
-        .path()
-        .app_config_dir()
-        .map_err(|e| AppError::Config(format!("Failed to resolve config dir: {}", e)))?;
-    Ok(dir.join(CONFIG_FILE))
+    config_file_path(app, CONFIG_FILE)
 }
 
 fn normalize_api_base(value: &str) -> String {
     value.trim().trim_end_matches('/').to_string()
 }
 
-pub fn load_api_base(app: &AppHandle) -> String {
+/// 校验配置名：1-32 个字母、数字、`-` 或 `_`（会用于文件名与同步游标键）
+pub fn validate_profile_name(name: &str) -> AppResult<String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(AppError::Validation(
+            "配置名只能包含字母、数字、- 和 _，长度 1-32".to_string(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// 读取完整 API 配置，缺失或损坏时使用默认值
+pub fn load_api_config(app: &AppHandle) -> ApiConfig {
     let path = match config_path(app) {
         Ok(path) => path,
         Err(err) => {
             tracing::warn!("Failed to resolve config path: {}", err);
-            return DEFAULT_API_BASE.to_string();
+            return ApiConfig::default();
         }
     };
 
     let content = match fs::read_to_string(&path) {
         Ok(content) => content,
-        Err(_) => return DEFAULT_API_BASE.to_string(),
+        Err(_) => return ApiConfig::default(),
     };
 
-    let config: ApiConfig = match serde_json::from_str(&content) {
+    let mut config: ApiConfig = match serde_json::from_str::<ApiConfig>(&content) {
         Ok(config) => config,
-        Err(err) => {
-            tracing::warn!("Failed to parse api config: {}", err);
-            return DEFAULT_API_BASE.to_string();
-        }
+        Err(_) => match serde_json::from_str::<LegacyApiConfig>(&content) {
+            Ok(legacy) => {
+                tracing::info!("Migrating legacy api config to profile '{}'", DEFAULT_PROFILE);
+                legacy.into()
+            }
+            Err(err) => {
+                tracing::warn!("Failed to parse api config: {}", err);
+                return ApiConfig::default();
+            }
+        },
     };
 
-    let normalized = normalize_api_base(&config.api_base);
-    if normalized.is_empty() {
-        DEFAULT_API_BASE.to_string()
-    } else {
-        normalized
+    for profile in &mut config.profiles {
+        profile.api_base = normalize_api_base(&profile.api_base);
+        if profile.api_base.is_empty() {
+            profile.api_base = DEFAULT_API_BASE.to_string();
+        }
     }
+    if config.profiles.is_empty() {
+        config = ApiConfig::default();
+    }
+    config
 }
 
-pub fn save_api_base(app: &AppHandle, api_base: &str) -> AppResult<String> {
+/// 当前生效的服务器配置
+pub fn load_active_profile(app: &AppHandle) -> ApiProfile {
+    load_api_config(app).active()
+}
+
+pub fn load_api_base(app: &AppHandle) -> String {
+    load_active_profile(app).api_base
+}
+
+fn write_api_config(app: &AppHandle, config: &ApiConfig) -> AppResult<()> {
     let path = config_path(app)?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| AppError::Config(format!("Failed to create config dir: {}", e)))?;
     }
 
+    let payload = serde_json::to_string_pretty(config)
+        .map_err(|e| AppError::Config(format!("Failed to serialize config: {}", e)))?;
+    fs::write(&path, payload)
+        .map_err(|e| AppError::Config(format!("Failed to write config: {}", e)))?;
+    Ok(())
+}
+
+/// 修改当前配置的 api_base
+pub fn save_api_base(app: &AppHandle, api_base: &str) -> AppResult<String> {
     let normalized = normalize_api_base(api_base);
     let value = if normalized.is_empty() {
         DEFAULT_API_BASE.to_string()
@@ -68,13 +199,76 @@ pub fn save_api_base(app: &AppHandle, api_base: &str) -> AppResult<String> {
         normalized
     };
 
-    let config = ApiConfig {
-        api_base: value.clone(),
-    };
-    let payload = serde_json::to_string_pretty(&config)
-        .map_err(|e| AppError::Config(format!("Failed to serialize config: {}", e)))?;
-    fs::write(&path, payload)
-        .map_err(|e| AppError::Config(format!("Failed to write config: {}", e)))?;
+    let mut config = load_api_config(app);
+    config.active_mut().api_base = value.clone();
+    write_api_config(app, &config)?;
 
     Ok(value)
 }
+
+/// 保存当前配置的 TLS 选项（CA、证书指纹、是否允许 HTTP），保留 api_base 不变
+pub fn save_api_security(
+    app: &AppHandle,
+    ca_bundle: Option<String>,
+    spki_pins: Vec<String>,
+    allow_insecure_http: bool,
+) -> AppResult<ApiProfile> {
+    let mut config = load_api_config(app);
+    let profile = config.active_mut();
+    profile.ca_bundle = ca_bundle.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    profile.spki_pins = spki_pins
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    profile.allow_insecure_http = allow_insecure_http;
+    let profile = profile.clone();
+    write_api_config(app, &config)?;
+
+    tracing::info!(
+        "API security updated for '{}': ca_bundle={:?}, pins={}, allow_insecure_http={}",
+        profile.name, profile.ca_bundle, profile.spki_pins.len(), profile.allow_insecure_http
+    );
+    Ok(profile)
+}
+
+/// 新增或更新一个服务器配置（按 name 匹配）
+pub fn save_profile(app: &AppHandle, mut profile: ApiProfile) -> AppResult<ApiConfig> {
+    profile.name = validate_profile_name(&profile.name)?;
+    profile.api_base = normalize_api_base(&profile.api_base);
+
+    let mut config = load_api_config(app);
+    match config.profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => *existing = profile,
+        None => config.profiles.push(profile),
+    }
+    write_api_config(app, &config)?;
+    Ok(config)
+}
+
+/// 删除服务器配置（不能删除当前配置）
+pub fn delete_profile(app: &AppHandle, name: &str) -> AppResult<ApiConfig> {
+    let mut config = load_api_config(app);
+    if config.active_profile == name {
+        return Err(AppError::Validation("不能删除当前使用的服务器配置".to_string()));
+    }
+    let before = config.profiles.len();
+    config.profiles.retain(|p| p.name != name);
+    if config.profiles.len() == before {
+        return Err(AppError::Validation(format!("服务器配置不存在: {}", name)));
+    }
+    write_api_config(app, &config)?;
+    Ok(config)
+}
+
+/// 切换当前配置，返回切换前的配置名
+pub fn set_active_profile(app: &AppHandle, name: &str) -> AppResult<String> {
+    let mut config = load_api_config(app);
+    if !config.profiles.iter().any(|p| p.name == name) {
+        return Err(AppError::Validation(format!("服务器配置不存在: {}", name)));
+    }
+    let previous = config.active().name;
+    config.active_profile = name.to_string();
+    write_api_config(app, &config)?;
+    Ok(previous)
+}
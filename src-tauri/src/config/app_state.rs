@@ -1,6 +1,140 @@
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
 use crate::db::DbPool;
+use crate::dto::{ChangeSource, TodosChanged};
+use crate::tray::refresh_tray;
+use crate::utils::AppResult;
+use sqd_core::storage::DbConnection;
+use sqd_core::Storage;
+use tauri::async_runtime::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// 任务变更事件名，载荷为 `TodosChanged`
+pub const TODOS_CHANGED_EVENT: &str = "todos-changed";
+
+/// 当前工作区的存储：任务写操作提交后通知所有窗口并刷新托盘
+#[derive(Clone)]
+pub struct AppStorage {
+    pool: DbPool,
+    app: AppHandle,
+    source: ChangeSource,
+}
+
+impl AppStorage {
+    /// 指定审计日志中记录的写操作来源（默认 `ui`）
+    pub fn with_source(mut self, source: ChangeSource) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+impl Storage for AppStorage {
+    fn connection(&self) -> AppResult<DbConnection> {
+        self.pool.connection()
+    }
+
+    fn change_source(&self) -> ChangeSource {
+        self.source
+    }
+
+    fn todos_changed(&self, changes: &TodosChanged) {
+        if let Err(e) = self.app.emit(TODOS_CHANGED_EVENT, changes) {
+            tracing::warn!("Failed to emit {}: {}", TODOS_CHANGED_EVENT, e);
+        }
+        refresh_tray(&self.app);
+    }
+}
+
+/// 已打开的工作区连接池
+struct OpenWorkspaces {
+    active: String,
+    pools: HashMap<String, DbPool>,
+}
 
 /// 应用全局状态
 pub struct AppState {
-    pub pool: DbPool,
+    app: AppHandle,
+    workspaces: RwLock<OpenWorkspaces>,
+    /// 同步与切换服务器配置 / 工作区互斥：切换期间同步暂停
+    pub sync_lock: Mutex<()>,
+}
+
+impl AppState {
+    pub fn new(app: AppHandle, workspace_id: &str, pool: DbPool) -> Self {
+        let mut pools = HashMap::new();
+        pools.insert(workspace_id.to_string(), pool);
+        AppState {
+            app,
+            workspaces: RwLock::new(OpenWorkspaces {
+                active: workspace_id.to_string(),
+                pools,
+            }),
+            sync_lock: Default::default(),
+        }
+    }
+
+    /// 当前工作区的连接池
+    pub fn pool(&self) -> DbPool {
+        let workspaces = self.workspaces.read().unwrap_or_else(PoisonError::into_inner);
+        workspaces
+            .pools
+            .get(&workspaces.active)
+            .cloned()
+            .expect("active workspace pool is open")
+    }
+
+    /// 当前工作区的存储（写操作会发出 `todos-changed`）
+    pub fn storage(&self) -> AppStorage {
+        AppStorage {
+            pool: self.pool(),
+            app: self.app.clone(),
+            source: ChangeSource::Ui,
+        }
+    }
+
+    /// 当前工作区 id
+    pub fn active_workspace(&self) -> String {
+        self.workspaces
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .active
+            .clone()
+    }
+
+    /// 已打开的工作区连接池
+    pub fn workspace_pool(&self, id: &str) -> Option<DbPool> {
+        self.workspaces
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pools
+            .get(id)
+            .cloned()
+    }
+
+    /// 所有已打开的工作区（id, 连接池）
+    pub fn open_pools(&self) -> Vec<(String, DbPool)> {
+        self.workspaces
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pools
+            .iter()
+            .map(|(id, pool)| (id.clone(), pool.clone()))
+            .collect()
+    }
+
+    /// 切换当前工作区
+    pub fn activate(&self, id: &str, pool: DbPool) {
+        let mut workspaces = self.workspaces.write().unwrap_or_else(PoisonError::into_inner);
+        workspaces.pools.insert(id.to_string(), pool);
+        workspaces.active = id.to_string();
+    }
+
+    /// 关闭工作区连接池（不能关闭当前工作区）
+    pub fn close(&self, id: &str) {
+        let mut workspaces = self.workspaces.write().unwrap_or_else(PoisonError::into_inner);
+        if workspaces.active != id {
+            workspaces.pools.remove(id);
+        }
+    }
 }
@@ -3,3 +3,21 @@ pub const QUICK_ADD_WIDTH: f64 = 480.0;
 pub const QUICK_ADD_HEIGHT: f64 = 60.0;
 pub const STATS_WIDTH: f64 = 900.0;
 pub const STATS_HEIGHT: f64 = 700.0;
+
+/// 托盘菜单最多列出的进行中任务数
+pub const TRAY_MAX_TASKS: usize = 5;
+/// 托盘菜单中任务名称的最大字符数
+pub const TRAY_TASK_LABEL_CHARS: usize = 30;
+
+/// 同步请求超时
+pub const SYNC_TIMEOUT_SECS: u64 = 30;
+/// 设置服务器地址时的连通性探测超时
+pub const PROBE_TIMEOUT_SECS: u64 = 5;
+/// access token 剩余有效期低于该值时提前刷新
+pub const TOKEN_REFRESH_LEEWAY_SECS: i64 = 60;
+
+/// 后台墓碑清理间隔（6 小时）
+pub const TOMBSTONE_PURGE_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// 后台重复任务检查间隔（15 分钟）
+pub const RECURRENCE_CHECK_INTERVAL_SECS: u64 = 15 * 60;
@@ -1,5 +1,8 @@
 pub mod app_state;
 pub mod api_base;
 pub mod constants;
+pub mod paths;
+pub mod settings;
+pub mod workspace;
 
-pub use app_state::AppState;
+pub use app_state::{AppState, AppStorage};
@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+use crate::utils::{AppError, AppResult};
+
+/// 配置目录下指定文件的路径
+pub fn config_file_path(app: &AppHandle, file_name: &str) -> AppResult<PathBuf> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| AppError::Config(format!("Failed to resolve config dir: {}", e)))?;
+    Ok(dir.join(file_name))
+}
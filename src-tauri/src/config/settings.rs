@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use super::paths::config_file_path;
+use crate::shortcuts;
+use crate::utils::{AppError, AppResult};
+
+const SETTINGS_FILE: &str = "assistant_settings.json";
+/// 当前设置结构版本，结构变化时递增并在 `MIGRATIONS` 末尾追加迁移
+pub const SETTINGS_VERSION: u32 = 2;
+
+pub const DEFAULT_QUICK_ADD_SHORTCUT: &str = "CommandOrControl+Shift+N";
+pub const DEFAULT_SYNC_INTERVAL_SECS: u64 = 60;
+/// 自动同步间隔上限（1 天）；0 表示关闭自动同步
+const MAX_SYNC_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const MIN_SYNC_INTERVAL_SECS: u64 = 10;
+const MAX_BROKER_LENGTH: usize = 100;
+
+/// 用户设置（`assistant_settings.json`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub version: u32,
+    /// 全局快捷键：动作 id → 快捷键，未列出的动作不绑定
+    pub shortcuts: BTreeMap<String, String>,
+    /// 自动同步间隔（秒），0 表示关闭
+    pub sync_interval_secs: u64,
+    /// 新建任务时默认选中的券商，空字符串表示沿用上次选择
+    pub default_broker: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            shortcuts: BTreeMap::from([(
+                shortcuts::QUICK_ADD.to_string(),
+                DEFAULT_QUICK_ADD_SHORTCUT.to_string(),
+            )]),
+            sync_interval_secs: DEFAULT_SYNC_INTERVAL_SECS,
+            default_broker: String::new(),
+        }
+    }
+}
+
+/// 更新设置的输入，未提供的字段保持不变
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsPatch {
+    /// 整体替换快捷键绑定；值为空字符串表示解除绑定
+    pub shortcuts: Option<BTreeMap<String, String>>,
+    pub sync_interval_secs: Option<u64>,
+    pub default_broker: Option<String>,
+}
+
+/// 迁移函数：把版本 i 的原始 JSON 升级到版本 i + 1
+type Migration = fn(&mut Value);
+
+/// 按顺序排列的迁移，下标即起始版本
+const MIGRATIONS: [Migration; SETTINGS_VERSION as usize] = [migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// v0：没有 version 字段的文件，字段与 v1 相同
+fn migrate_v0_to_v1(_value: &mut Value) {}
+
+/// v2：单个 `quickAddShortcut` 改为按动作配置的 `shortcuts`
+fn migrate_v1_to_v2(value: &mut Value) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    let quick_add = map
+        .remove("quickAddShortcut")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_QUICK_ADD_SHORTCUT.to_string());
+    let mut shortcuts = serde_json::Map::new();
+    shortcuts.insert(shortcuts::QUICK_ADD.to_string(), Value::from(quick_add));
+    map.insert("shortcuts".to_string(), Value::Object(shortcuts));
+}
+
+fn settings_path(app: &AppHandle) -> AppResult<PathBuf> {
+    config_file_path(app, SETTINGS_FILE)
+}
+
+fn stored_version(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+/// 将原始 JSON 迁移到当前版本，返回是否发生了迁移
+fn migrate(value: &mut Value) -> bool {
+    let from = stored_version(value);
+    if from > SETTINGS_VERSION {
+        tracing::warn!(
+            "Settings version {} is newer than supported {}, unknown fields are ignored",
+            from,
+            SETTINGS_VERSION
+        );
+        return false;
+    }
+
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        tracing::info!("Migrating settings v{} -> v{}", version, version + 1);
+        migration(value);
+    }
+    if let Value::Object(map) = value {
+        map.insert("version".to_string(), Value::from(SETTINGS_VERSION));
+    }
+    from < SETTINGS_VERSION
+}
+
+/// 读取设置，缺失或损坏时使用默认值；旧版本文件会迁移并写回
+pub fn load_settings(app: &AppHandle) -> Settings {
+    let path = match settings_path(app) {
+        Ok(path) => path,
+        Err(err) => {
+            tracing::warn!("Failed to resolve settings path: {}", err);
+            return Settings::default();
+        }
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Settings::default(),
+    };
+
+    let mut value: Value = match serde_json::from_str(&content) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("Failed to parse settings: {}", err);
+            return Settings::default();
+        }
+    };
+
+    let migrated = migrate(&mut value);
+    let settings = match serde_json::from_value::<Settings>(value) {
+        Ok(settings) => settings,
+        Err(err) => {
+            tracing::warn!("Invalid settings, using defaults: {}", err);
+            return Settings::default();
+        }
+    };
+
+    if migrated {
+        if let Err(err) = save_settings(app, &settings) {
+            tracing::warn!("Failed to persist migrated settings: {}", err);
+        }
+    }
+    settings
+}
+
+/// 写入设置
+pub fn save_settings(app: &AppHandle, settings: &Settings) -> AppResult<()> {
+    let path = settings_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| AppError::Config(format!("Failed to create config dir: {}", e)))?;
+    }
+
+    let payload = serde_json::to_string_pretty(settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+    fs::write(&path, payload)
+        .map_err(|e| AppError::Config(format!("Failed to write settings: {}", e)))?;
+    Ok(())
+}
+
+/// 去掉空绑定并校验快捷键（动作是否存在、格式、是否互相冲突）
+fn validate_shortcuts(bindings: BTreeMap<String, String>) -> AppResult<BTreeMap<String, String>> {
+    let bindings: BTreeMap<String, String> = bindings
+        .into_iter()
+        .map(|(action, shortcut)| (action, shortcut.trim().to_string()))
+        .filter(|(_, shortcut)| !shortcut.is_empty())
+        .collect();
+    shortcuts::parse_bindings(&bindings)?;
+    Ok(bindings)
+}
+
+fn validate_sync_interval(secs: u64) -> AppResult<u64> {
+    if secs != 0 && !(MIN_SYNC_INTERVAL_SECS..=MAX_SYNC_INTERVAL_SECS).contains(&secs) {
+        return Err(AppError::Validation(format!(
+            "同步间隔需为 0（关闭）或 {}-{} 秒",
+            MIN_SYNC_INTERVAL_SECS, MAX_SYNC_INTERVAL_SECS
+        )));
+    }
+    Ok(secs)
+}
+
+fn validate_default_broker(value: &str) -> AppResult<String> {
+    let value = value.trim();
+    if value.chars().count() > MAX_BROKER_LENGTH {
+        return Err(AppError::Validation(format!(
+            "默认券商不能超过 {} 个字符",
+            MAX_BROKER_LENGTH
+        )));
+    }
+    Ok(value.to_string())
+}
+
+/// 校验设置修改并合并到 `settings`（不写入文件）
+pub fn apply_patch(settings: &mut Settings, patch: SettingsPatch) -> AppResult<()> {
+    if let Some(bindings) = patch.shortcuts {
+        settings.shortcuts = validate_shortcuts(bindings)?;
+    }
+    if let Some(secs) = patch.sync_interval_secs {
+        settings.sync_interval_secs = validate_sync_interval(secs)?;
+    }
+    if let Some(broker) = patch.default_broker {
+        settings.default_broker = validate_default_broker(&broker)?;
+    }
+    settings.version = SETTINGS_VERSION;
+    Ok(())
+}
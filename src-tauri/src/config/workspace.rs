@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use super::api_base;
+use super::paths::config_file_path;
+use crate::db::{try_establish_connection, DbPool};
+use crate::utils::{AppError, AppResult};
+use sqd_core::Storage;
+
+pub const DEFAULT_WORKSPACE: &str = "default";
+const DEFAULT_WORKSPACE_NAME: &str = "默认";
+const CONFIG_FILE: &str = "assistant_workspaces.json";
+const WORKSPACE_DIR: &str = "workspaces";
+/// 引入工作区之前的数据库文件，首次启动时迁移为默认工作区
+const LEGACY_DATABASE_FILE: &str = "database.db";
+/// SQLite 主文件之外可能存在的附属文件
+const SQLITE_SIDE_SUFFIXES: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+/// 本地工作区：独立的数据库文件 + 绑定的服务器配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    /// 稳定标识，用作数据库文件名；重命名不会改变
+    pub id: String,
+    pub name: String,
+    /// 使用的服务器配置名（见 `api_base::ApiProfile`）
+    pub profile: String,
+}
+
+/// `assistant_workspaces.json` 的内容：工作区列表 + 当前工作区
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    pub active_workspace: String,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl WorkspaceConfig {
+    fn with_default(profile: String) -> Self {
+        WorkspaceConfig {
+            active_workspace: DEFAULT_WORKSPACE.to_string(),
+            workspaces: vec![Workspace {
+                id: DEFAULT_WORKSPACE.to_string(),
+                name: DEFAULT_WORKSPACE_NAME.to_string(),
+                profile,
+            }],
+        }
+    }
+
+    /// 当前工作区
+    pub fn active(&self) -> Workspace {
+        self.find(&self.active_workspace)
+            .or_else(|| self.workspaces.first())
+            .cloned()
+            .unwrap_or_else(|| Workspace {
+                id: DEFAULT_WORKSPACE.to_string(),
+                name: DEFAULT_WORKSPACE_NAME.to_string(),
+                profile: api_base::DEFAULT_PROFILE.to_string(),
+            })
+    }
+
+    pub fn find(&self, id: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.id == id)
+    }
+
+    fn find_mut(&mut self, id: &str) -> AppResult<&mut Workspace> {
+        self.workspaces
+            .iter_mut()
+            .find(|w| w.id == id)
+            .ok_or_else(|| AppError::Validation(format!("工作区不存在: {}", id)))
+    }
+}
+
+fn config_path(app: &AppHandle) -> AppResult<PathBuf> {
+    config_file_path(app, CONFIG_FILE)
+}
+
+/// 读取工作区配置，缺失或损坏时只包含默认工作区（沿用当前服务器配置）
+pub fn load_workspace_config(app: &AppHandle) -> WorkspaceConfig {
+    let fallback = || WorkspaceConfig::with_default(api_base::load_active_profile(app).name);
+
+    let path = match config_path(app) {
+        Ok(path) => path,
+        Err(err) => {
+            tracing::warn!("Failed to resolve workspace config path: {}", err);
+            return fallback();
+        }
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return fallback(),
+    };
+
+    match serde_json::from_str::<WorkspaceConfig>(&content) {
+        Ok(config) if !config.workspaces.is_empty() => config,
+        Ok(_) => fallback(),
+        Err(err) => {
+            tracing::warn!("Failed to parse workspace config: {}", err);
+            fallback()
+        }
+    }
+}
+
+fn write_workspace_config(app: &AppHandle, config: &WorkspaceConfig) -> AppResult<()> {
+    let path = config_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| AppError::Config(format!("Failed to create config dir: {}", e)))?;
+    }
+
+    let payload = serde_json::to_string_pretty(config)
+        .map_err(|e| AppError::Config(format!("Failed to serialize workspaces: {}", e)))?;
+    fs::write(&path, payload)
+        .map_err(|e| AppError::Config(format!("Failed to write workspaces: {}", e)))?;
+    Ok(())
+}
+
+fn app_data_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| AppError::Config(format!("Failed to resolve app data dir: {}", e)))
+}
+
+/// 工作区数据库文件路径：`app_data_dir/workspaces/<id>.db`
+pub fn database_path(app: &AppHandle, id: &str) -> AppResult<PathBuf> {
+    let dir = app_data_dir(app)?.join(WORKSPACE_DIR);
+    fs::create_dir_all(&dir)
+        .map_err(|e| AppError::Config(format!("Failed to create workspace dir: {}", e)))?;
+    Ok(dir.join(format!("{}.db", id)))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 把旧版 `database.db` 移到默认工作区（仅在默认工作区数据库尚不存在时）
+pub fn migrate_legacy_database(app: &AppHandle) -> AppResult<()> {
+    let legacy = app_data_dir(app)?.join(LEGACY_DATABASE_FILE);
+    let target = database_path(app, DEFAULT_WORKSPACE)?;
+    if !legacy.exists() || target.exists() {
+        return Ok(());
+    }
+
+    tracing::info!("Moving legacy database {:?} to workspace '{}'", legacy, DEFAULT_WORKSPACE);
+    fs::rename(&legacy, &target)
+        .map_err(|e| AppError::Config(format!("Failed to move legacy database: {}", e)))?;
+    for suffix in SQLITE_SIDE_SUFFIXES {
+        let side = with_suffix(&legacy, suffix);
+        if side.exists() {
+            fs::rename(&side, with_suffix(&target, suffix))
+                .map_err(|e| AppError::Config(format!("Failed to move {:?}: {}", side, e)))?;
+        }
+    }
+    Ok(())
+}
+
+/// 打开工作区数据库并执行迁移
+pub fn open_pool(app: &AppHandle, id: &str) -> AppResult<DbPool> {
+    let path = database_path(app, id)?;
+    let url = path
+        .to_str()
+        .ok_or_else(|| AppError::Config(format!("Invalid database path: {:?}", path)))?;
+    let pool = try_establish_connection(url)?;
+    pool.run_migrations()?;
+    tracing::info!("Opened workspace '{}' at {}", id, url);
+    Ok(pool)
+}
+
+/// 校验工作区名称：去除首尾空白后 1-32 个字符，且不能与其他工作区重名
+fn validate_workspace_name(config: &WorkspaceConfig, id: Option<&str>, name: &str) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 32 {
+        return Err(AppError::Validation("工作区名称长度需为 1-32 个字符".to_string()));
+    }
+    if config
+        .workspaces
+        .iter()
+        .any(|w| w.name == name && Some(w.id.as_str()) != id)
+    {
+        return Err(AppError::Validation(format!("工作区名称已存在: {}", name)));
+    }
+    Ok(name.to_string())
+}
+
+fn ensure_profile_exists(app: &AppHandle, profile: &str) -> AppResult<()> {
+    if api_base::load_api_config(app).profiles.iter().any(|p| p.name == profile) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("服务器配置不存在: {}", profile)))
+    }
+}
+
+/// 新建工作区（未指定服务器配置时使用当前配置）
+pub fn create_workspace(app: &AppHandle, name: &str, profile: Option<String>) -> AppResult<Workspace> {
+    let mut config = load_workspace_config(app);
+    let name = validate_workspace_name(&config, None, name)?;
+    let profile = match profile {
+        Some(profile) => {
+            ensure_profile_exists(app, &profile)?;
+            profile
+        }
+        None => api_base::load_active_profile(app).name,
+    };
+
+    let workspace = Workspace {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        profile,
+    };
+    config.workspaces.push(workspace.clone());
+    write_workspace_config(app, &config)?;
+    Ok(workspace)
+}
+
+/// 重命名工作区（只修改显示名称，数据库文件不变）
+pub fn rename_workspace(app: &AppHandle, id: &str, name: &str) -> AppResult<WorkspaceConfig> {
+    let mut config = load_workspace_config(app);
+    let name = validate_workspace_name(&config, Some(id), name)?;
+    config.find_mut(id)?.name = name;
+    write_workspace_config(app, &config)?;
+    Ok(config)
+}
+
+/// 设置当前工作区
+pub fn set_active_workspace(app: &AppHandle, id: &str) -> AppResult<Workspace> {
+    let mut config = load_workspace_config(app);
+    let workspace = config.find_mut(id)?.clone();
+    config.active_workspace = workspace.id.clone();
+    write_workspace_config(app, &config)?;
+    Ok(workspace)
+}
+
+/// 修改工作区绑定的服务器配置
+pub fn set_workspace_profile(app: &AppHandle, id: &str, profile: &str) -> AppResult<()> {
+    let mut config = load_workspace_config(app);
+    config.find_mut(id)?.profile = profile.to_string();
+    write_workspace_config(app, &config)
+}
+
+/// 服务器配置是否被某个工作区使用
+pub fn is_profile_in_use(app: &AppHandle, profile: &str) -> bool {
+    load_workspace_config(app)
+        .workspaces
+        .iter()
+        .any(|w| w.profile == profile)
+}
+
+/// 删除工作区及其数据库文件（不能删除当前工作区）
+pub fn delete_workspace(app: &AppHandle, id: &str) -> AppResult<WorkspaceConfig> {
+    let mut config = load_workspace_config(app);
+    if config.active_workspace == id {
+        return Err(AppError::Validation("不能删除当前使用的工作区".to_string()));
+    }
+    config.find_mut(id)?;
+    config.workspaces.retain(|w| w.id != id);
+    write_workspace_config(app, &config)?;
+
+    let path = database_path(app, id)?;
+    for file in std::iter::once(path.clone())
+        .chain(SQLITE_SIDE_SUFFIXES.iter().map(|suffix| with_suffix(&path, suffix)))
+    {
+        if file.exists() {
+            if let Err(e) = fs::remove_file(&file) {
+                tracing::warn!("Failed to remove workspace file {:?}: {}", file, e);
+            }
+        }
+    }
+    Ok(config)
+}
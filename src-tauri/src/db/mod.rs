@@ -1,7 +0,0 @@
-pub mod connection;
-pub mod models;
-pub mod schema;
-
-pub use connection::{establish_connection, get_connection, DbPool};
-pub use models::{NewTodo, Todo, UpdateTodo};
-pub use schema::todos;
@@ -1,3 +0,0 @@
-pub mod todo_dto;
-
-pub use todo_dto::*;
@@ -0,0 +1,22 @@
+use tauri::AppHandle;
+
+use crate::sync::auth::{self, AuthStatus};
+use crate::utils::AppResult;
+
+/// 登录命令
+#[tauri::command]
+pub async fn login(app: AppHandle, username: String, password: String) -> AppResult<AuthStatus> {
+    auth::login(&app, &username, &password).await
+}
+
+/// 注销命令
+#[tauri::command]
+pub async fn logout(app: AppHandle) -> AppResult<()> {
+    auth::logout(&app).await
+}
+
+/// 获取登录状态命令
+#[tauri::command]
+pub fn get_auth_status(app: AppHandle) -> AppResult<AuthStatus> {
+    auth::status(&app)
+}
@@ -6,5 +6,5 @@ use tauri::State;
 /// 获取券商池命令
 #[tauri::command]
 pub fn get_broker_pool(state: State<AppState>) -> AppResult<Vec<String>> {
-    BrokerService::get_pool(&state.pool)
+    BrokerService::get_pool(&state.pool())
 }
@@ -0,0 +1,47 @@
+use crate::config::AppState;
+use crate::dto::{AddTodoItemInput, Checklist, DeleteTodoItemInput, ReorderTodoItemsInput, UpdateTodoItemInput};
+use crate::services::ChecklistService;
+use crate::utils::AppResult;
+use tauri::State;
+
+/// 获取任务检查项命令
+#[tauri::command]
+pub fn get_todo_items(state: State<AppState>, todo_id: String) -> AppResult<Checklist> {
+    ChecklistService::list(&state.pool(), &todo_id)
+}
+
+/// 添加检查项命令
+#[tauri::command]
+pub fn add_todo_item(
+    state: State<AppState>,
+    input: AddTodoItemInput,
+) -> AppResult<Checklist> {
+    ChecklistService::add(&state.storage(), input)
+}
+
+/// 修改检查项命令（内容或勾选状态）
+#[tauri::command]
+pub fn update_todo_item(
+    state: State<AppState>,
+    input: UpdateTodoItemInput,
+) -> AppResult<Checklist> {
+    ChecklistService::update(&state.storage(), input)
+}
+
+/// 删除检查项命令
+#[tauri::command]
+pub fn delete_todo_item(
+    state: State<AppState>,
+    input: DeleteTodoItemInput,
+) -> AppResult<Checklist> {
+    ChecklistService::delete(&state.storage(), input)
+}
+
+/// 调整检查项顺序命令
+#[tauri::command]
+pub fn reorder_todo_items(
+    state: State<AppState>,
+    input: ReorderTodoItemsInput,
+) -> AppResult<Checklist> {
+    ChecklistService::reorder(&state.storage(), input)
+}
@@ -1,6 +1,11 @@
-use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
 
-use crate::config::api_base;
+use crate::config::api_base::{self, ApiConfig, ApiProfile};
+use crate::config::AppState;
+use crate::handlers::profile_handler::reset_profile_sync;
+use crate::sync::probe::{self, ProbeResult};
+use crate::sync::{http, tls};
 use crate::utils::AppResult;
 
 /// 获取 API 基础地址
@@ -9,8 +14,96 @@ pub fn get_api_base(app: AppHandle) -> AppResult<String> {
     Ok(api_base::load_api_base(&app))
 }
 
-/// 设置 API 基础地址
+/// 设置 API 基础地址的结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetApiBaseResult {
+    /// 当前生效的地址（探测失败且未强制时仍为旧值）
+    pub api_base: String,
+    pub saved: bool,
+    pub probe: ProbeResult,
+}
+
+/// 探测服务器地址（不保存）
+#[tauri::command]
+pub async fn test_api_base(app: AppHandle, api_base: String) -> AppResult<ProbeResult> {
+    let mut profile = api_base::load_active_profile(&app);
+    profile.api_base = probe::validate_api_base(&api_base)?;
+    probe::probe(&profile).await
+}
+
+/// 设置 API 基础地址：先校验并探测，探测失败时保留旧值，除非 `force`
+///
+/// 地址变化时重置当前配置的同步状态（见 `reset_profile_sync`）。
+#[tauri::command]
+pub async fn set_api_base(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    api_base: String,
+    force: Option<bool>,
+) -> AppResult<SetApiBaseResult> {
+    let current = api_base::load_active_profile(&app);
+    let mut candidate = current.clone();
+    candidate.api_base = probe::validate_api_base(&api_base)?;
+
+    // 被明文 HTTP 策略拒绝的地址即使强制也不保存
+    let probe_result = probe::probe(&candidate).await?;
+    let insecure = http::ensure_secure(&candidate).is_err();
+    if insecure || (!probe_result.reachable && !force.unwrap_or(false)) {
+        tracing::warn!(
+            "Keeping api base {}: probe of {} failed",
+            current.api_base, candidate.api_base
+        );
+        return Ok(SetApiBaseResult {
+            api_base: current.api_base,
+            saved: false,
+            probe: probe_result,
+        });
+    }
+
+    let _guard = state.sync_lock.lock().await;
+    let current = api_base::load_active_profile(&app);
+    if current.api_base != candidate.api_base {
+        tracing::info!(
+            "Profile '{}' moved from {} to {}, resetting sync state",
+            current.name, current.api_base, candidate.api_base
+        );
+        reset_profile_sync(&app, &state, &current.name)?;
+    }
+    let saved = api_base::save_api_base(&app, &candidate.api_base)?;
+    Ok(SetApiBaseResult {
+        api_base: saved,
+        saved: true,
+        probe: probe_result,
+    })
+}
+
+/// 获取全部服务器配置与当前配置名
+#[tauri::command]
+pub fn get_api_config(app: AppHandle) -> AppResult<ApiConfig> {
+    Ok(api_base::load_api_config(&app))
+}
+
+/// 设置 API 安全选项的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSecurityInput {
+    pub ca_bundle: Option<String>,
+    #[serde(default)]
+    pub spki_pins: Vec<String>,
+    #[serde(default)]
+    pub allow_insecure_http: bool,
+}
+
+/// 设置当前配置的安全选项（保存前校验 CA 文件与指纹格式）
 #[tauri::command]
-pub fn set_api_base(app: AppHandle, api_base: String) -> AppResult<String> {
-    api_base::save_api_base(&app, &api_base)
+pub fn set_api_security(app: AppHandle, input: ApiSecurityInput) -> AppResult<ApiProfile> {
+    tls::validate_options(input.ca_bundle.as_deref(), &input.spki_pins)?;
+
+    api_base::save_api_security(
+        &app,
+        input.ca_bundle,
+        input.spki_pins,
+        input.allow_insecure_http,
+    )
 }
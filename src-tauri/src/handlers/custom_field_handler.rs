@@ -0,0 +1,50 @@
+use crate::config::AppState;
+use crate::dto::{
+    CreateCustomFieldInput, CustomFieldView, DeleteCustomFieldInput, ReorderCustomFieldsInput,
+    UpdateCustomFieldInput,
+};
+use crate::services::CustomFieldService;
+use crate::utils::AppResult;
+use tauri::State;
+
+/// 获取自定义字段定义命令
+#[tauri::command]
+pub fn list_custom_fields(state: State<AppState>) -> AppResult<Vec<CustomFieldView>> {
+    CustomFieldService::list(&state.pool())
+}
+
+/// 创建自定义字段命令
+#[tauri::command]
+pub fn create_custom_field(
+    state: State<AppState>,
+    input: CreateCustomFieldInput,
+) -> AppResult<CustomFieldView> {
+    CustomFieldService::create(&state.pool(), input)
+}
+
+/// 修改自定义字段命令（名称与选项）
+#[tauri::command]
+pub fn update_custom_field(
+    state: State<AppState>,
+    input: UpdateCustomFieldInput,
+) -> AppResult<CustomFieldView> {
+    CustomFieldService::update(&state.pool(), input)
+}
+
+/// 删除自定义字段命令（任务中的值保留）
+#[tauri::command]
+pub fn delete_custom_field(
+    state: State<AppState>,
+    input: DeleteCustomFieldInput,
+) -> AppResult<()> {
+    CustomFieldService::delete(&state.pool(), input)
+}
+
+/// 重排自定义字段命令
+#[tauri::command]
+pub fn reorder_custom_fields(
+    state: State<AppState>,
+    input: ReorderCustomFieldsInput,
+) -> AppResult<Vec<CustomFieldView>> {
+    CustomFieldService::reorder(&state.pool(), input)
+}
@@ -0,0 +1,29 @@
+use crate::config::AppState;
+use crate::dto::{CreateLinkInput, DeleteLinkInput, TodoDetail};
+use crate::services::LinkService;
+use crate::utils::AppResult;
+use tauri::State;
+
+/// 获取任务详情命令（检查项与关联任务）
+#[tauri::command]
+pub fn get_todo_detail(state: State<AppState>, todo_id: String) -> AppResult<TodoDetail> {
+    LinkService::detail(&state.pool(), &todo_id)
+}
+
+/// 创建任务关联命令
+#[tauri::command]
+pub fn create_todo_link(
+    state: State<AppState>,
+    input: CreateLinkInput,
+) -> AppResult<TodoDetail> {
+    LinkService::create(&state.pool(), input)
+}
+
+/// 删除任务关联命令
+#[tauri::command]
+pub fn delete_todo_link(
+    state: State<AppState>,
+    input: DeleteLinkInput,
+) -> AppResult<()> {
+    LinkService::delete(&state.pool(), input)
+}
@@ -1,7 +1,29 @@
 pub mod todo_handler;
 pub mod broker_handler;
 pub mod config_handler;
+pub mod sync_handler;
+pub mod workspace_handler;
+pub mod auth_handler;
+pub mod profile_handler;
+pub mod settings_handler;
+pub mod series_handler;
+pub mod template_handler;
+pub mod checklist_handler;
+pub mod link_handler;
+pub mod tag_handler;
+pub mod custom_field_handler;
 
 pub use todo_handler::*;
 pub use broker_handler::*;
 pub use config_handler::*;
+pub use sync_handler::*;
+pub use workspace_handler::*;
+pub use auth_handler::*;
+pub use profile_handler::*;
+pub use settings_handler::*;
+pub use series_handler::*;
+pub use template_handler::*;
+pub use checklist_handler::*;
+pub use link_handler::*;
+pub use tag_handler::*;
+pub use custom_field_handler::*;
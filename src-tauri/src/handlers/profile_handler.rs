@@ -0,0 +1,117 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::config::api_base::{self, ApiConfig, ApiProfile};
+use crate::config::workspace;
+use crate::config::AppState;
+use crate::services::SyncService;
+use crate::sync::{http, probe, tls};
+use crate::utils::{AppError, AppResult};
+
+/// 保存服务器配置的输入
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInput {
+    pub name: String,
+    pub api_base: String,
+    pub ca_bundle: Option<String>,
+    #[serde(default)]
+    pub spki_pins: Vec<String>,
+    #[serde(default)]
+    pub allow_insecure_http: bool,
+}
+
+/// 服务器地址变更后，清除所有使用该配置的工作区的同步游标与记录绑定
+///
+/// 调用方需持有 `AppState::sync_lock`；旧游标对新服务器没有意义，沿用会漏拉数据，
+/// 而 `synced_until` 超前会让清空回收站、清理墓碑删掉新服务器尚未收到的删除。
+pub(crate) fn reset_profile_sync(app: &AppHandle, state: &AppState, profile: &str) -> AppResult<()> {
+    let config = workspace::load_workspace_config(app);
+    for w in config.workspaces.iter().filter(|w| w.profile == profile) {
+        let pool = match state.workspace_pool(&w.id) {
+            Some(pool) => pool,
+            None => workspace::open_pool(app, &w.id)?,
+        };
+        SyncService::reset_profile(&pool, profile)?;
+    }
+    Ok(())
+}
+
+/// 新增或更新服务器配置
+///
+/// 保存前校验 CA 文件、证书指纹与明文 HTTP 策略（同 `set_api_security`）；
+/// 修改已有配置的地址时重置其同步状态。
+#[tauri::command]
+pub async fn save_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: ProfileInput,
+) -> AppResult<ApiConfig> {
+    let profile = ApiProfile {
+        name: api_base::validate_profile_name(&input.name)?,
+        api_base: probe::validate_api_base(&input.api_base)?,
+        ca_bundle: input.ca_bundle.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+        spki_pins: input
+            .spki_pins
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        allow_insecure_http: input.allow_insecure_http,
+    };
+    tls::validate_options(profile.ca_bundle.as_deref(), &profile.spki_pins)?;
+    http::ensure_secure(&profile)?;
+
+    let _guard = state.sync_lock.lock().await;
+    let previous = api_base::load_api_config(&app)
+        .profiles
+        .into_iter()
+        .find(|p| p.name == profile.name);
+    if let Some(previous) = previous.filter(|p| p.api_base != profile.api_base) {
+        tracing::info!(
+            "Profile '{}' moved from {} to {}, resetting sync state",
+            previous.name, previous.api_base, profile.api_base
+        );
+        reset_profile_sync(&app, &state, &previous.name)?;
+    }
+    api_base::save_profile(&app, profile)
+}
+
+/// 删除服务器配置（仍被工作区使用时拒绝）
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, name: String) -> AppResult<ApiConfig> {
+    if workspace::is_profile_in_use(&app, &name) {
+        return Err(AppError::Validation(format!("服务器配置仍被工作区使用: {}", name)));
+    }
+    api_base::delete_profile(&app, &name)
+}
+
+/// 切换服务器配置
+///
+/// 等待进行中的同步结束并在切换期间阻止新的同步；切换前把尚未绑定的本地记录
+/// 归属到旧配置，保证它们不会被推送到新服务器。
+#[tauri::command]
+pub async fn switch_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+) -> AppResult<ApiProfile> {
+    let _guard = state.sync_lock.lock().await;
+
+    let current = api_base::load_active_profile(&app);
+    if current.name == name {
+        return Ok(current);
+    }
+    if !api_base::load_api_config(&app).profiles.iter().any(|p| p.name == name) {
+        return Err(AppError::Validation(format!("服务器配置不存在: {}", name)));
+    }
+
+    SyncService::bind_unbound(&state.pool(), &current.name)?;
+    api_base::set_active_profile(&app, &name)?;
+    workspace::set_workspace_profile(&app, &state.active_workspace(), &name)?;
+
+    let profile = api_base::load_active_profile(&app);
+    tracing::info!("Switched sync profile: {} -> {}", current.name, profile.name);
+    let _ = app.emit("sync-profile-changed", &profile.name);
+    Ok(profile)
+}
@@ -0,0 +1,39 @@
+use crate::config::AppState;
+use crate::db::Todo;
+use crate::dto::{CreateSeriesInput, SeriesView, StopSeriesInput, UpdateSeriesInput};
+use crate::services::RecurrenceService;
+use crate::utils::AppResult;
+use tauri::State;
+
+/// 创建重复任务系列命令（返回第一个实例）
+#[tauri::command]
+pub fn create_todo_series(
+    state: State<AppState>,
+    input: CreateSeriesInput,
+) -> AppResult<Todo> {
+    RecurrenceService::create_series(&state.storage(), input)
+}
+
+/// 获取重复任务系列命令
+#[tauri::command]
+pub fn list_todo_series(state: State<AppState>) -> AppResult<Vec<SeriesView>> {
+    RecurrenceService::list_series(&state.pool())
+}
+
+/// 修改重复任务系列命令
+#[tauri::command]
+pub fn update_todo_series(
+    state: State<AppState>,
+    input: UpdateSeriesInput,
+) -> AppResult<SeriesView> {
+    RecurrenceService::update_series(&state.storage(), input)
+}
+
+/// 停止重复任务系列命令
+#[tauri::command]
+pub fn stop_todo_series(
+    state: State<AppState>,
+    input: StopSeriesInput,
+) -> AppResult<SeriesView> {
+    RecurrenceService::stop_series(&state.storage(), input)
+}
@@ -0,0 +1,47 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::config::settings::{self, Settings, SettingsPatch};
+use crate::shortcuts::{self, ShortcutAction, ShortcutRegistry, ShortcutStatus};
+use crate::utils::AppResult;
+
+/// 获取用户设置
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> AppResult<Settings> {
+    Ok(settings::load_settings(&app))
+}
+
+/// 修改用户设置并通知所有窗口
+///
+/// 快捷键有变化时立即重新注册；注册失败则恢复原快捷键并返回错误，设置不会保存。
+#[tauri::command]
+pub fn update_settings(app: AppHandle, patch: SettingsPatch) -> AppResult<Settings> {
+    let previous = settings::load_settings(&app);
+    let mut updated = previous.clone();
+    settings::apply_patch(&mut updated, patch)?;
+
+    if updated.shortcuts != previous.shortcuts {
+        if let Err(e) = shortcuts::apply(&app, &updated.shortcuts) {
+            if let Err(restore) = shortcuts::apply(&app, &previous.shortcuts) {
+                tracing::error!("Failed to restore previous shortcuts: {}", restore);
+            }
+            return Err(e);
+        }
+    }
+
+    settings::save_settings(&app, &updated)?;
+    tracing::info!("Settings updated: {:?}", updated);
+    let _ = app.emit("settings-changed", &updated);
+    Ok(updated)
+}
+
+/// 获取全局快捷键注册状态
+#[tauri::command]
+pub fn get_shortcut_status(registry: State<ShortcutRegistry>) -> AppResult<Vec<ShortcutStatus>> {
+    Ok(registry.status())
+}
+
+/// 可绑定全局快捷键的动作列表
+#[tauri::command]
+pub fn list_shortcut_actions() -> AppResult<&'static [ShortcutAction]> {
+    Ok(shortcuts::ACTIONS)
+}
@@ -0,0 +1,34 @@
+use crate::config::AppState;
+use crate::dto::{PurgeReport, SyncReport};
+use crate::services::SyncService;
+use crate::sync;
+use crate::utils::AppResult;
+use tauri::{AppHandle, State};
+
+/// 立即与服务端同步
+#[tauri::command]
+pub async fn sync_now(app: AppHandle, state: State<'_, AppState>) -> AppResult<SyncReport> {
+    let _guard = state.sync_lock.lock().await;
+    sync::client::sync_now(&app, state.storage()).await
+}
+
+/// 获取墓碑保留天数
+#[tauri::command]
+pub fn get_tombstone_retention(state: State<AppState>) -> AppResult<u32> {
+    SyncService::get_retention_days(&state.pool())
+}
+
+/// 设置墓碑保留天数
+#[tauri::command]
+pub fn set_tombstone_retention(state: State<AppState>, days: u32) -> AppResult<u32> {
+    SyncService::set_retention_days(&state.pool(), days)
+}
+
+/// 手动清理已同步的软删除记录
+#[tauri::command]
+pub fn purge_deleted(
+    state: State<AppState>,
+    retention_days: Option<u32>,
+) -> AppResult<PurgeReport> {
+    SyncService::purge_deleted(&state.pool(), retention_days)
+}
@@ -0,0 +1,51 @@
+use crate::config::AppState;
+use crate::db::Tag;
+use crate::dto::{
+    CreateTagInput, DeleteTagInput, MergeTagsInput, SetTodoTagsInput, TagView, UpdateTagInput,
+};
+use crate::services::TagService;
+use crate::utils::AppResult;
+use std::collections::HashMap;
+use tauri::State;
+
+/// 获取所有标签命令（含任务数）
+#[tauri::command]
+pub fn list_tags(state: State<AppState>) -> AppResult<Vec<TagView>> {
+    TagService::list(&state.pool())
+}
+
+/// 创建标签命令
+#[tauri::command]
+pub fn create_tag(state: State<AppState>, input: CreateTagInput) -> AppResult<Tag> {
+    TagService::create(&state.pool(), input)
+}
+
+/// 修改标签命令（重命名或改颜色）
+#[tauri::command]
+pub fn update_tag(state: State<AppState>, input: UpdateTagInput) -> AppResult<Tag> {
+    TagService::update(&state.pool(), input)
+}
+
+/// 删除标签命令
+#[tauri::command]
+pub fn delete_tag(state: State<AppState>, input: DeleteTagInput) -> AppResult<()> {
+    TagService::delete(&state.pool(), input)
+}
+
+/// 合并标签命令
+#[tauri::command]
+pub fn merge_tags(state: State<AppState>, input: MergeTagsInput) -> AppResult<Tag> {
+    TagService::merge(&state.pool(), input)
+}
+
+/// 设置任务标签命令
+#[tauri::command]
+pub fn set_todo_tags(state: State<AppState>, input: SetTodoTagsInput) -> AppResult<Vec<String>> {
+    TagService::set_todo_tags(&state.pool(), input)
+}
+
+/// 获取所有任务的标签命令（任务 id → 标签 id）
+#[tauri::command]
+pub fn get_todo_tag_map(state: State<AppState>) -> AppResult<HashMap<String, Vec<String>>> {
+    TagService::todo_tag_map(&state.pool())
+}
@@ -0,0 +1,55 @@
+use crate::config::AppState;
+use crate::db::Todo;
+use crate::dto::{CreateTemplateInput, DeleteTemplateInput, InstantiateTemplateInput, TemplateView, UpdateTemplateInput};
+use crate::services::TemplateService;
+use crate::utils::AppResult;
+use tauri::State;
+
+/// 获取任务模板命令
+#[tauri::command]
+pub fn list_todo_templates(state: State<AppState>) -> AppResult<Vec<TemplateView>> {
+    TemplateService::list(&state.pool())
+}
+
+/// 创建任务模板命令
+#[tauri::command]
+pub fn create_todo_template(
+    state: State<AppState>,
+    input: CreateTemplateInput,
+) -> AppResult<TemplateView> {
+    TemplateService::create(&state.pool(), input)
+}
+
+/// 修改任务模板命令
+#[tauri::command]
+pub fn update_todo_template(
+    state: State<AppState>,
+    input: UpdateTemplateInput,
+) -> AppResult<TemplateView> {
+    TemplateService::update(&state.pool(), input)
+}
+
+/// 删除任务模板命令
+#[tauri::command]
+pub fn delete_todo_template(
+    state: State<AppState>,
+    input: DeleteTemplateInput,
+) -> AppResult<()> {
+    TemplateService::delete(&state.pool(), input)
+}
+
+/// 按模板为券商批量创建任务命令
+#[tauri::command]
+pub fn instantiate_template(
+    state: State<AppState>,
+    template_id: String,
+    broker: String,
+) -> AppResult<Vec<Todo>> {
+    TemplateService::instantiate(&state.storage(), InstantiateTemplateInput { template_id, broker })
+}
+
+/// 导出所有模板为 JSON 命令
+#[tauri::command]
+pub fn export_todo_templates(state: State<AppState>) -> AppResult<String> {
+    TemplateService::export_json(&state.pool())
+}
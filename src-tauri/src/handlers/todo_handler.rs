@@ -1,8 +1,9 @@
 use crate::config::AppState;
 use crate::db::Todo;
-use crate::dto::{CreateTodoInput, UpdateTodoInput, DeleteTodoInput, SearchTodoInput};
-use crate::services::TodoService;
+use crate::dto::{AuditExportInput, BulkDeleteTodosInput, BulkItemResult, BulkUpdateTodosInput, ChangeSource, CreateTodoInput, UpdateTodoInput, DeleteTodoInput, EmptyTrashReport, FieldFilter, ListTodosInput, RestoreTodoInput, SearchTodoInput, TodoHistoryInput, TodoHistoryPage, UpsertSummary};
+use crate::services::{AuditService, JournalService, TodoService};
 use crate::utils::AppResult;
+use serde_json::{Map, Value};
 use tauri::State;
 
 /// 创建 Todo 命令
@@ -13,15 +14,23 @@ pub fn create_todo(
     status: String,
     broker: String,
     conclusion: Option<String>,
+    custom_fields: Option<Map<String, Value>>,
+    source: Option<ChangeSource>,
 ) -> AppResult<Todo> {
-    let input = CreateTodoInput { title, status, broker, conclusion };
-    TodoService::create(&state.pool, input)
+    let input = CreateTodoInput { title, status, broker, conclusion, custom_fields };
+    let storage = state.storage().with_source(source.unwrap_or_default());
+    TodoService::create(&storage, input)
 }
 
-/// 获取所有 Todos 命令
+/// 获取 Todos 命令（可按标签与自定义字段过滤）
 #[tauri::command]
-pub fn get_todos(state: State<AppState>) -> AppResult<Vec<Todo>> {
-    TodoService::get_all(&state.pool)
+pub fn get_todos(
+    state: State<AppState>,
+    tag_ids: Option<Vec<String>>,
+    field_filter: Option<FieldFilter>,
+) -> AppResult<Vec<Todo>> {
+    let input = ListTodosInput { tag_ids: tag_ids.unwrap_or_default(), field_filter };
+    TodoService::list(&state.pool(), input)
 }
 
 /// 更新 Todo 命令
@@ -30,7 +39,7 @@ pub fn update_todo(
     state: State<AppState>,
     input: UpdateTodoInput,
 ) -> AppResult<Todo> {
-    TodoService::update(&state.pool, input)
+    TodoService::update(&state.storage(), input)
 }
 
 /// 删除 Todo 命令
@@ -39,14 +48,70 @@ pub fn delete_todo(
     state: State<AppState>,
     input: DeleteTodoInput,
 ) -> AppResult<()> {
-    TodoService::delete(&state.pool, input)
+    TodoService::delete(&state.storage(), input)
+}
+
+/// 批量更新 Todo 命令（逐个返回结果）
+#[tauri::command]
+pub fn bulk_update_todos(
+    state: State<AppState>,
+    input: BulkUpdateTodosInput,
+) -> AppResult<Vec<BulkItemResult>> {
+    TodoService::bulk_update(&state.storage(), input)
+}
+
+/// 批量删除 Todo 命令（逐个返回结果）
+#[tauri::command]
+pub fn bulk_delete_todos(
+    state: State<AppState>,
+    input: BulkDeleteTodosInput,
+) -> AppResult<Vec<BulkItemResult>> {
+    TodoService::bulk_delete(&state.storage(), input)
+}
+
+/// 获取回收站列表命令
+#[tauri::command]
+pub fn list_deleted_todos(state: State<AppState>) -> AppResult<Vec<Todo>> {
+    TodoService::get_deleted(&state.pool())
+}
+
+/// 恢复 Todo 命令
+#[tauri::command]
+pub fn restore_todo(
+    state: State<AppState>,
+    input: RestoreTodoInput,
+) -> AppResult<Todo> {
+    TodoService::restore(&state.storage(), input)
+}
+
+/// 撤销本次运行中最近一次任务操作，没有可撤销的操作时返回 null
+#[tauri::command]
+pub fn undo_todo(state: State<AppState>) -> AppResult<Option<Todo>> {
+    JournalService::undo(&state.storage())
+}
+
+/// 重做最近一次撤销的任务操作
+#[tauri::command]
+pub fn redo_todo(state: State<AppState>) -> AppResult<Option<Todo>> {
+    JournalService::redo(&state.storage())
+}
+
+/// 清空回收站命令（配置了同步时，尚未推送的删除保留至下次同步后，并在结果中返回）
+#[tauri::command]
+pub fn empty_trash(state: State<AppState>) -> AppResult<EmptyTrashReport> {
+    TodoService::empty_trash(&state.storage())
 }
 
 /// 搜索 Todos 命令
 #[tauri::command]
-pub fn search_todos(state: State<AppState>, query: String) -> AppResult<Vec<Todo>> {
-    let input = SearchTodoInput { query };
-    TodoService::search(&state.pool, input)
+pub fn search_todos(
+    state: State<AppState>,
+    query: String,
+    tag_ids: Option<Vec<String>>,
+    field_filter: Option<FieldFilter>,
+) -> AppResult<Vec<Todo>> {
+    let input = SearchTodoInput { query, tag_ids: tag_ids.unwrap_or_default(), field_filter };
+    TodoService::search(&state.pool(), input)
 }
 
 /// 获取增量变更（包含已删除）
@@ -55,7 +120,7 @@ pub fn get_todos_updated_after(
     state: State<AppState>,
     updated_after: Option<String>,
 ) -> AppResult<Vec<Todo>> {
-    TodoService::get_updated_after(&state.pool, updated_after)
+    TodoService::get_updated_after(&state.pool(), updated_after)
 }
 
 /// 批量 upsert（用于同步）
@@ -63,6 +128,24 @@ pub fn get_todos_updated_after(
 pub fn upsert_todos(
     state: State<AppState>,
     todos: Vec<Todo>,
-) -> AppResult<()> {
-    TodoService::upsert_batch(&state.pool, todos)
+) -> AppResult<UpsertSummary> {
+    TodoService::upsert_batch(&state.storage(), todos, ChangeSource::Sync)
+}
+
+/// 分页获取任务的变更历史（审计日志）
+#[tauri::command]
+pub fn get_todo_history(
+    state: State<AppState>,
+    input: TodoHistoryInput,
+) -> AppResult<TodoHistoryPage> {
+    AuditService::history(&state.pool(), input)
+}
+
+/// 导出审计日志（CSV 文本）
+#[tauri::command]
+pub fn export_todo_audit(
+    state: State<AppState>,
+    input: Option<AuditExportInput>,
+) -> AppResult<String> {
+    AuditService::export_csv(&state.pool(), input.unwrap_or_default())
 }
@@ -0,0 +1,105 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::config::api_base;
+use crate::config::workspace::{self, Workspace, WorkspaceConfig};
+use crate::config::AppState;
+use crate::tray::refresh_tray;
+use crate::utils::{AppError, AppResult};
+
+/// 新建工作区的输入
+#[derive(Debug, Deserialize)]
+pub struct CreateWorkspaceInput {
+    pub name: String,
+    /// 绑定的服务器配置，缺省时使用当前配置
+    pub profile: Option<String>,
+}
+
+/// 获取工作区列表
+#[tauri::command]
+pub fn list_workspaces(app: AppHandle) -> AppResult<WorkspaceConfig> {
+    Ok(workspace::load_workspace_config(&app))
+}
+
+/// 新建工作区（不会切换过去）
+#[tauri::command]
+pub fn create_workspace(app: AppHandle, input: CreateWorkspaceInput) -> AppResult<Workspace> {
+    let created = workspace::create_workspace(&app, &input.name, input.profile)?;
+    // 立即建库，路径或迁移有问题时在创建阶段就能发现
+    workspace::open_pool(&app, &created.id)?;
+    tracing::info!("Workspace created: {} ({})", created.name, created.id);
+    Ok(created)
+}
+
+/// 重命名工作区
+#[tauri::command]
+pub fn rename_workspace(app: AppHandle, id: String, name: String) -> AppResult<WorkspaceConfig> {
+    workspace::rename_workspace(&app, &id, &name)
+}
+
+/// 删除工作区及其数据库（不能删除当前工作区）
+#[tauri::command]
+pub async fn delete_workspace(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> AppResult<WorkspaceConfig> {
+    let _guard = state.sync_lock.lock().await;
+    if state.active_workspace() == id {
+        return Err(AppError::Validation("不能删除当前使用的工作区".to_string()));
+    }
+    state.close(&id);
+    let config = workspace::delete_workspace(&app, &id)?;
+    tracing::info!("Workspace deleted: {}", id);
+    Ok(config)
+}
+
+/// 切换工作区
+///
+/// 切换期间暂停同步；同时切换到该工作区绑定的服务器配置，完成后通知所有窗口重新加载。
+#[tauri::command]
+pub async fn switch_workspace(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> AppResult<Workspace> {
+    let _guard = state.sync_lock.lock().await;
+
+    let config = workspace::load_workspace_config(&app);
+    let target = config
+        .find(&id)
+        .cloned()
+        .ok_or_else(|| AppError::Validation(format!("工作区不存在: {}", id)))?;
+    if state.active_workspace() == target.id {
+        return Ok(target);
+    }
+
+    let pool = match state.workspace_pool(&target.id) {
+        Some(pool) => pool,
+        None => workspace::open_pool(&app, &target.id)?,
+    };
+
+    // 绑定的服务器配置已被删除时退回默认配置
+    let mut target = target;
+    let profile_exists = api_base::load_api_config(&app)
+        .profiles
+        .iter()
+        .any(|p| p.name == target.profile);
+    if !profile_exists {
+        tracing::warn!(
+            "Profile '{}' of workspace '{}' no longer exists, using '{}'",
+            target.profile, target.id, api_base::DEFAULT_PROFILE
+        );
+        workspace::set_workspace_profile(&app, &target.id, api_base::DEFAULT_PROFILE)?;
+        target.profile = api_base::DEFAULT_PROFILE.to_string();
+    }
+    api_base::set_active_profile(&app, &target.profile)?;
+
+    workspace::set_active_workspace(&app, &target.id)?;
+    state.activate(&target.id, pool);
+    refresh_tray(&app);
+
+    tracing::info!("Switched workspace: {} ({})", target.name, target.id);
+    let _ = app.emit("workspace-changed", &target);
+    Ok(target)
+}
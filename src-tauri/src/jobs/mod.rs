@@ -0,0 +1,5 @@
+pub mod tombstone_purge;
+pub mod recurrence;
+
+pub use tombstone_purge::spawn_tombstone_purge_job;
+pub use recurrence::spawn_recurrence_job;
@@ -0,0 +1,44 @@
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::config::constants::RECURRENCE_CHECK_INTERVAL_SECS;
+use crate::config::AppState;
+use crate::services::RecurrenceService;
+
+/// 启动后台重复任务生成（启动时执行一次，之后按固定间隔执行，覆盖所有已打开的工作区）
+pub fn spawn_recurrence_job(app: AppHandle) {
+    let result = thread::Builder::new()
+        .name("recurrence".to_string())
+        .spawn(move || loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                let active = state.active_workspace();
+                for (workspace, pool) in state.open_pools() {
+                    // 当前工作区通过 AppStorage 生成，以便通知前端和托盘
+                    let result = if workspace == active {
+                        RecurrenceService::generate_due(&state.storage())
+                    } else {
+                        RecurrenceService::generate_due(&pool)
+                    };
+                    match result {
+                        Ok(created) => tracing::debug!(
+                            "Recurrence job generated {} todos in workspace '{}'",
+                            created.len(),
+                            workspace
+                        ),
+                        Err(e) => tracing::warn!(
+                            "Recurrence job failed in workspace '{}': {}",
+                            workspace,
+                            e
+                        ),
+                    }
+                }
+            }
+            thread::sleep(Duration::from_secs(RECURRENCE_CHECK_INTERVAL_SECS));
+        });
+
+    if let Err(e) = result {
+        tracing::error!("Failed to spawn recurrence job: {}", e);
+    }
+}
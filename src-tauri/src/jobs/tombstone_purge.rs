@@ -0,0 +1,39 @@
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::config::constants::TOMBSTONE_PURGE_INTERVAL_SECS;
+use crate::config::AppState;
+use crate::services::SyncService;
+
+/// 启动后台墓碑清理任务（启动时执行一次，之后按固定间隔执行，覆盖所有已打开的工作区）
+pub fn spawn_tombstone_purge_job(app: AppHandle) {
+    let result = thread::Builder::new()
+        .name("tombstone-purge".to_string())
+        .spawn(move || loop {
+            let pools = app
+                .try_state::<AppState>()
+                .map(|state| state.open_pools())
+                .unwrap_or_default();
+            for (workspace, pool) in pools {
+                match SyncService::purge_deleted(&pool, None) {
+                    Ok(report) => tracing::debug!(
+                        "Background tombstone purge removed {} rows in workspace '{}'",
+                        report.purged_ids.len(),
+                        workspace
+                    ),
+                    Err(e) => tracing::warn!(
+                        "Background tombstone purge failed in workspace '{}': {}",
+                        workspace,
+                        e
+                    ),
+                }
+            }
+            thread::sleep(Duration::from_secs(TOMBSTONE_PURGE_INTERVAL_SECS));
+        });
+
+    if let Err(e) = result {
+        tracing::error!("Failed to spawn tombstone purge job: {}", e);
+    }
+}
@@ -1,22 +1,20 @@
 // 模块声明
 mod config;
-mod db;
-mod dto;
 mod handlers;
-mod services;
+mod jobs;
+mod shortcuts;
+mod sync;
+mod tray;
 mod utils;
 mod window;
 
+// 业务逻辑位于 sqd-core，本 crate 只负责 Tauri 适配
+use sqd_core::{db, dto, services};
+
 use config::AppState;
 use db::establish_connection;
-use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use sqd_core::Storage;
 use tauri::Manager;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
-use tauri::tray::TrayIconBuilder;
-use tauri_plugin_global_shortcut::GlobalShortcutExt;
-use window::{create_or_show_quick_add_window, create_or_show_stats_window};
-
-const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -44,7 +42,15 @@ pub fn run() {
             tracing::info!("Application starting...");
             tracing::info!("App data directory: {:?}", app_dir);
 
-            let db_path = app_dir.join("database.db");
+            // 每个工作区一个数据库：app_data_dir/workspaces/<id>.db
+            if let Err(e) = config::workspace::migrate_legacy_database(app.handle()) {
+                tracing::error!("Failed to migrate legacy database: {}", e);
+            }
+            let workspace = config::workspace::load_workspace_config(app.handle()).active();
+            tracing::info!("Active workspace: {} ({})", workspace.name, workspace.id);
+
+            let db_path = config::workspace::database_path(app.handle(), &workspace.id)
+                .expect("Failed to resolve workspace database path");
             let database_url = db_path.to_str().expect("Invalid database path");
 
             tracing::info!("Database path: {}", database_url);
@@ -53,10 +59,7 @@ pub fn run() {
             let pool = establish_connection(database_url);
 
             // 运行 Diesel 迁移
-            match pool.get()
-                .expect("Failed to get connection")
-                .run_pending_migrations(MIGRATIONS)
-            {
+            match pool.run_migrations() {
                 Ok(_) => tracing::info!("Migrations applied successfully"),
                 Err(e) => {
                     // 如果表已存在，仅记录警告而不是崩溃
@@ -64,62 +67,24 @@ pub fn run() {
                 }
             }
 
-            let app_state = AppState { pool };
+            let app_state = AppState::new(app.handle().clone(), &workspace.id, pool);
             app.manage(app_state);
 
-            // 注册全局快捷键
-            let app_handle = app.handle().clone();
-            app.global_shortcut().on_shortcut("CommandOrControl+Shift+N", move |_app, _event, _shortcut| {
-                tracing::info!("Global shortcut triggered: CommandOrControl+Shift+N");
-                create_or_show_quick_add_window(&app_handle);
-            }).expect("Failed to register global shortcut");
-
-            // 创建托盘菜单
-            let show_item = MenuItem::with_id(app, "show", "显示主窗口", true, None::<&str>)?;
-            let stats_item = MenuItem::with_id(app, "stats", "数据统计", true, None::<&str>)?;
-            let quick_add_item = MenuItem::with_id(app, "quick_add", "快速添加", true, None::<&str>)?;
-            let separator = PredefinedMenuItem::separator(app)?;
-            let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-
-            let menu = Menu::with_items(app, &[
-                &show_item,
-                &stats_item,
-                &quick_add_item,
-                &separator,
-                &quit_item,
-            ])?;
-
-            // 构建托盘图标
-            let _tray = TrayIconBuilder::new()
-                .icon(app.default_window_icon().unwrap().clone())
-                .menu(&menu)
-                .tooltip("SQD 工作助手")
-                .on_menu_event(move |app, event| {
-                    match event.id.as_ref() {
-                        "show" => {
-                            tracing::info!("Tray menu: Show main window");
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                                let _ = window.unminimize();
-                            }
-                        }
-                        "stats" => {
-                            tracing::info!("Tray menu: Stats view");
-                            create_or_show_stats_window(app);
-                        }
-                        "quick_add" => {
-                            tracing::info!("Tray menu: Quick add");
-                            create_or_show_quick_add_window(app);
-                        }
-                        "quit" => {
-                            tracing::info!("Tray menu: Quit");
-                            app.exit(0);
-                        }
-                        _ => {}
-                    }
-                })
-                .build(app)?;
+            // 后台清理已同步的软删除记录
+            jobs::spawn_tombstone_purge_job(app.handle().clone());
+
+            // 按计划生成重复任务的实例
+            jobs::spawn_recurrence_job(app.handle().clone());
+
+            // 注册全局快捷键（失败不影响启动，前端可通过 get_shortcut_status 查看）
+            app.manage(shortcuts::ShortcutRegistry::default());
+            let settings = config::settings::load_settings(app.handle());
+            if let Err(e) = shortcuts::apply(app.handle(), &settings.shortcuts) {
+                tracing::error!("{}", e);
+            }
+
+            // 创建托盘（菜单随任务变化重建）
+            tray::create_tray(app)?;
 
             // 监听 macOS Dock 图标点击事件
             #[cfg(target_os = "macos")]
@@ -135,12 +100,73 @@ pub fn run() {
             handlers::get_todos,
             handlers::update_todo,
             handlers::delete_todo,
+            handlers::bulk_update_todos,
+            handlers::bulk_delete_todos,
             handlers::search_todos,
+            handlers::list_deleted_todos,
+            handlers::restore_todo,
+            handlers::undo_todo,
+            handlers::redo_todo,
+            handlers::get_todo_history,
+            handlers::export_todo_audit,
+            handlers::create_todo_series,
+            handlers::list_todo_series,
+            handlers::update_todo_series,
+            handlers::stop_todo_series,
+            handlers::list_todo_templates,
+            handlers::create_todo_template,
+            handlers::update_todo_template,
+            handlers::delete_todo_template,
+            handlers::instantiate_template,
+            handlers::export_todo_templates,
+            handlers::get_todo_items,
+            handlers::add_todo_item,
+            handlers::update_todo_item,
+            handlers::delete_todo_item,
+            handlers::reorder_todo_items,
+            handlers::get_todo_detail,
+            handlers::create_todo_link,
+            handlers::delete_todo_link,
+            handlers::list_tags,
+            handlers::create_tag,
+            handlers::update_tag,
+            handlers::delete_tag,
+            handlers::merge_tags,
+            handlers::set_todo_tags,
+            handlers::get_todo_tag_map,
+            handlers::list_custom_fields,
+            handlers::create_custom_field,
+            handlers::update_custom_field,
+            handlers::delete_custom_field,
+            handlers::reorder_custom_fields,
+            handlers::empty_trash,
             handlers::get_todos_updated_after,
             handlers::upsert_todos,
+            handlers::sync_now,
+            handlers::login,
+            handlers::logout,
+            handlers::get_auth_status,
+            handlers::get_tombstone_retention,
+            handlers::set_tombstone_retention,
+            handlers::purge_deleted,
             handlers::get_broker_pool,
             handlers::get_api_base,
             handlers::set_api_base,
+            handlers::test_api_base,
+            handlers::get_api_config,
+            handlers::save_profile,
+            handlers::delete_profile,
+            handlers::switch_profile,
+            handlers::set_api_security,
+            handlers::get_settings,
+            handlers::update_settings,
+            handlers::get_shortcut_status,
+            handlers::list_shortcut_actions,
+            handlers::list_workspaces,
+            handlers::create_workspace,
+            handlers::rename_workspace,
+            handlers::switch_workspace,
+            handlers::delete_workspace,
             utils::logger::log_from_frontend,
         ])
         .build(tauri::generate_context!())
@@ -1,5 +0,0 @@
-pub mod todo_service;
-pub mod broker_service;
-
-pub use todo_service::TodoService;
-pub use broker_service::BrokerService;
@@ -0,0 +1,106 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::AppState;
+use crate::db::Todo;
+use crate::dto::UpdateTodoInput;
+use crate::services::{JournalService, TodoService};
+use crate::window::{create_or_show_quick_add_window, create_or_show_stats_window, show_main_window, toggle_main_window};
+
+pub const QUICK_ADD: &str = "quick_add";
+pub const TOGGLE_MAIN: &str = "toggle_main";
+pub const OPEN_STATS: &str = "open_stats";
+pub const START_PENDING: &str = "start_pending";
+pub const COMPLETE_CURRENT: &str = "complete_current";
+pub const UNDO: &str = "undo";
+pub const REDO: &str = "redo";
+
+/// 可以绑定全局快捷键的动作
+#[derive(Debug, Serialize)]
+pub struct ShortcutAction {
+    pub id: &'static str,
+    pub label: &'static str,
+    #[serde(skip)]
+    run: fn(&AppHandle),
+}
+
+impl ShortcutAction {
+    pub fn run(&self, app: &AppHandle) {
+        tracing::info!("Global shortcut triggered: {}", self.id);
+        (self.run)(app);
+    }
+}
+
+/// 动作注册表，新增动作时在这里追加
+pub const ACTIONS: &[ShortcutAction] = &[
+    ShortcutAction { id: QUICK_ADD, label: "快速添加", run: create_or_show_quick_add_window },
+    ShortcutAction { id: TOGGLE_MAIN, label: "显示/隐藏主窗口", run: toggle_main_window },
+    ShortcutAction { id: OPEN_STATS, label: "打开数据统计", run: create_or_show_stats_window },
+    ShortcutAction { id: START_PENDING, label: "开始最近的待办", run: start_latest_pending },
+    ShortcutAction { id: COMPLETE_CURRENT, label: "完成当前任务", run: complete_current },
+    ShortcutAction { id: UNDO, label: "撤销任务操作", run: undo },
+    ShortcutAction { id: REDO, label: "重做任务操作", run: redo },
+];
+
+pub fn find_action(id: &str) -> Option<&'static ShortcutAction> {
+    ACTIONS.iter().find(|action| action.id == id)
+}
+
+/// 把最近更新的待办任务设为进行中
+fn start_latest_pending(app: &AppHandle) {
+    let storage = app.state::<AppState>().storage();
+    let result = TodoService::latest_with_status(&storage, "pending").and_then(|todo| match todo {
+        Some(todo) => TodoService::update(
+            &storage,
+            UpdateTodoInput {
+                todo_id: todo.id,
+                title: None,
+                status: Some("in_progress".to_string()),
+                broker: None,
+                conclusion: None,
+                custom_fields: None,
+                expected_version: Some(todo.version),
+            },
+        )
+        .map(Some),
+        None => Ok(None),
+    });
+
+    match result {
+        Ok(Some(todo)) => tracing::info!("Started pending todo via shortcut: {}", todo.id),
+        Ok(None) => tracing::info!("No pending todo to start"),
+        Err(e) => tracing::error!("Failed to start pending todo: {}", e),
+    }
+}
+
+/// 完成最近更新的进行中任务：完成需要填写结论，因此显示主窗口并让其弹出结论对话框
+fn complete_current(app: &AppHandle) {
+    let storage = app.state::<AppState>().storage();
+    match TodoService::latest_with_status(&storage, "in_progress") {
+        Ok(Some(todo)) => request_complete(app, &todo),
+        Ok(None) => tracing::info!("No in-progress todo to complete"),
+        Err(e) => tracing::error!("Failed to load in-progress todo: {}", e),
+    }
+}
+
+/// 显示主窗口并请求其弹出结论对话框来完成任务
+pub fn request_complete(app: &AppHandle, todo: &Todo) {
+    show_main_window(app);
+    let _ = app.emit("complete-todo-requested", todo);
+}
+
+fn undo(app: &AppHandle) {
+    match JournalService::undo(&app.state::<AppState>().storage()) {
+        Ok(Some(todo)) => tracing::info!("Undid operation on todo {} via shortcut", todo.id),
+        Ok(None) => tracing::info!("Nothing to undo"),
+        Err(e) => tracing::error!("Failed to undo: {}", e),
+    }
+}
+
+fn redo(app: &AppHandle) {
+    match JournalService::redo(&app.state::<AppState>().storage()) {
+        Ok(Some(todo)) => tracing::info!("Redid operation on todo {} via shortcut", todo.id),
+        Ok(None) => tracing::info!("Nothing to redo"),
+        Err(e) => tracing::error!("Failed to redo: {}", e),
+    }
+}
@@ -0,0 +1,109 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use super::actions::{find_action, ShortcutAction};
+use crate::utils::{AppError, AppResult};
+
+/// 单个动作的快捷键注册结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+    pub action: String,
+    pub shortcut: String,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+/// 最近一次注册的结果，供前端展示注册失败的快捷键
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    status: Mutex<Vec<ShortcutStatus>>,
+}
+
+impl ShortcutRegistry {
+    pub fn status(&self) -> Vec<ShortcutStatus> {
+        self.status.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    fn set_status(&self, status: Vec<ShortcutStatus>) {
+        *self.status.lock().unwrap_or_else(PoisonError::into_inner) = status;
+    }
+}
+
+/// 解析并校验快捷键绑定：动作必须存在、格式有效、不同动作不能使用同一快捷键
+pub fn parse_bindings(
+    bindings: &BTreeMap<String, String>,
+) -> AppResult<Vec<(&'static ShortcutAction, Shortcut)>> {
+    let mut parsed: Vec<(&'static ShortcutAction, Shortcut)> = Vec::with_capacity(bindings.len());
+    for (action, value) in bindings {
+        let Some(registered) = find_action(action) else {
+            return Err(AppError::Validation(format!("未知的快捷键动作: {}", action)));
+        };
+        let shortcut = value
+            .parse::<Shortcut>()
+            .map_err(|e| AppError::Validation(format!("快捷键格式无效 '{}': {}", value, e)))?;
+        if let Some((other, _)) = parsed.iter().find(|(_, s)| s.id() == shortcut.id()) {
+            return Err(AppError::Validation(format!(
+                "快捷键冲突: {} 同时绑定到 {} 和 {}",
+                value, other.id, action
+            )));
+        }
+        parsed.push((registered, shortcut));
+    }
+    Ok(parsed)
+}
+
+/// 注销已有快捷键并按绑定重新注册，任一快捷键注册失败时返回错误（其余快捷键仍然生效）
+pub fn apply(app: &AppHandle, bindings: &BTreeMap<String, String>) -> AppResult<()> {
+    let parsed = parse_bindings(bindings)?;
+
+    let global_shortcut = app.global_shortcut();
+    if let Err(e) = global_shortcut.unregister_all() {
+        tracing::warn!("Failed to unregister global shortcuts: {}", e);
+    }
+
+    let mut status = Vec::with_capacity(parsed.len());
+    let mut failures = Vec::new();
+    for (action, shortcut) in parsed {
+        let value = bindings[action.id].clone();
+        let result = global_shortcut.on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                action.run(app);
+            }
+        });
+
+        let error = match result {
+            Ok(()) => {
+                tracing::info!("Global shortcut registered: {} -> {}", value, action.id);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Failed to register global shortcut {} for {}: {}", value, action.id, e);
+                failures.push(format!("{}（{}）", value, action.label));
+                Some(e.to_string())
+            }
+        };
+        status.push(ShortcutStatus {
+            action: action.id.to_string(),
+            shortcut: value,
+            registered: error.is_none(),
+            error,
+        });
+    }
+
+    if let Some(registry) = app.try_state::<ShortcutRegistry>() {
+        registry.set_status(status);
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Config(format!(
+            "快捷键注册失败，可能已被其他应用占用: {}",
+            failures.join("、")
+        )))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod actions;
+pub mod binding;
+
+pub use actions::{ShortcutAction, ACTIONS, QUICK_ADD};
+pub use binding::{apply, parse_bindings, ShortcutRegistry, ShortcutStatus};
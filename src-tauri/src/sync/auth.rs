@@ -0,0 +1,153 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Serialize;
+use sqd_core::dto::{LoginRequest, RefreshRequest, TokenResponse};
+use tauri::async_runtime::Mutex;
+use tauri::AppHandle;
+
+use super::http::{read_json, send, SyncEndpoint};
+use super::token_store::{clear_tokens, load_tokens, save_tokens, StoredTokens};
+use crate::config::api_base::load_active_profile;
+use crate::config::constants::TOKEN_REFRESH_LEEWAY_SECS;
+use crate::utils::{AppError, AppResult};
+
+/// 串行化刷新：refresh token 只能用一次，并发请求同时遇到 401 时只有一个去换新令牌
+static REFRESH_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// 登录状态（返回给前端，不包含令牌本身）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthStatus {
+    pub logged_in: bool,
+    pub username: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+impl From<Option<StoredTokens>> for AuthStatus {
+    fn from(tokens: Option<StoredTokens>) -> Self {
+        match tokens {
+            Some(tokens) => AuthStatus {
+                logged_in: true,
+                username: Some(tokens.username),
+                expires_at: Some(tokens.expires_at),
+            },
+            None => AuthStatus {
+                logged_in: false,
+                username: None,
+                expires_at: None,
+            },
+        }
+    }
+}
+
+fn to_stored(username: &str, response: TokenResponse) -> StoredTokens {
+    let expires_at = (Utc::now() + Duration::seconds(response.expires_in))
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+    StoredTokens {
+        username: username.to_string(),
+        access_token: response.access_token,
+        refresh_token: response.refresh_token,
+        expires_at,
+    }
+}
+
+fn is_expiring(tokens: &StoredTokens) -> bool {
+    match DateTime::parse_from_rfc3339(&tokens.expires_at) {
+        Ok(expires_at) => {
+            expires_at.with_timezone(&Utc) - Utc::now()
+                < Duration::seconds(TOKEN_REFRESH_LEEWAY_SECS)
+        }
+        Err(_) => true,
+    }
+}
+
+/// 用户名密码换取令牌并加密保存
+pub async fn login(app: &AppHandle, username: &str, password: &str) -> AppResult<AuthStatus> {
+    let username = username.trim();
+    if username.is_empty() || password.is_empty() {
+        return Err(AppError::Validation("用户名和密码不能为空".to_string()));
+    }
+
+    let endpoint = SyncEndpoint::load(app)?;
+    let request = LoginRequest {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    let response = send(endpoint.client.post(endpoint.url("/auth/login")).json(&request)).await?;
+    let tokens: TokenResponse = read_json(response).await.map_err(|e| match e {
+        AppError::Unauthorized(_) => AppError::Unauthorized("用户名或密码错误".to_string()),
+        other => other,
+    })?;
+
+    let stored = to_stored(username, tokens);
+    save_tokens(app, &endpoint.profile, &stored)?;
+    tracing::info!("Logged in to profile {} as {}", endpoint.profile, username);
+    Ok(AuthStatus::from(Some(stored)))
+}
+
+/// 通知服务端注销（尽力而为）并删除当前配置的本地令牌
+pub async fn logout(app: &AppHandle) -> AppResult<()> {
+    let profile = load_active_profile(app).name;
+    if let Ok(Some(tokens)) = load_tokens(app, &profile) {
+        if let Ok(endpoint) = SyncEndpoint::load(app) {
+            let request = endpoint
+                .client
+                .post(endpoint.url("/auth/logout"))
+                .bearer_auth(&tokens.access_token);
+            if let Err(e) = send(request).await {
+                tracing::warn!("Failed to revoke token on server: {}", e);
+            }
+        }
+    }
+
+    clear_tokens(app, &profile)?;
+    tracing::info!("Logged out of profile {}", profile);
+    Ok(())
+}
+
+/// 当前配置的登录状态
+pub fn status(app: &AppHandle) -> AppResult<AuthStatus> {
+    let profile = load_active_profile(app).name;
+    Ok(AuthStatus::from(load_tokens(app, &profile)?))
+}
+
+/// 用 refresh token 换取新令牌；refresh token 失效时清除本地令牌并返回 Unauthorized
+///
+/// `stale_token` 是调用方手上已失效（或即将过期）的 access token；等锁期间若已被其他请求
+/// 刷新，直接返回新令牌，不再重复使用同一个 refresh token。
+pub async fn refresh(app: &AppHandle, endpoint: &SyncEndpoint, stale_token: &str) -> AppResult<String> {
+    let _guard = REFRESH_LOCK.lock().await;
+    let tokens = load_tokens(app, &endpoint.profile)?
+        .ok_or_else(|| AppError::Unauthorized("请先登录".to_string()))?;
+    if tokens.access_token != stale_token && !is_expiring(&tokens) {
+        return Ok(tokens.access_token);
+    }
+
+    let request = RefreshRequest {
+        refresh_token: tokens.refresh_token.clone(),
+    };
+    let response = send(endpoint.client.post(endpoint.url("/auth/refresh")).json(&request)).await?;
+    let refreshed: TokenResponse = match read_json(response).await {
+        Ok(refreshed) => refreshed,
+        Err(AppError::Unauthorized(_)) => {
+            clear_tokens(app, &endpoint.profile)?;
+            return Err(AppError::Unauthorized("登录已过期，请重新登录".to_string()));
+        }
+        Err(e) => return Err(e),
+    };
+
+    let stored = to_stored(&tokens.username, refreshed);
+    save_tokens(app, &endpoint.profile, &stored)?;
+    tracing::info!("Access token refreshed for {}", stored.username);
+    Ok(stored.access_token)
+}
+
+/// 获取可用的 access token（即将过期时自动刷新），未登录返回 None
+pub async fn access_token(app: &AppHandle, endpoint: &SyncEndpoint) -> AppResult<Option<String>> {
+    match load_tokens(app, &endpoint.profile)? {
+        Some(tokens) if is_expiring(&tokens) => {
+            refresh(app, endpoint, &tokens.access_token).await.map(Some)
+        }
+        Some(tokens) => Ok(Some(tokens.access_token)),
+        None => Ok(None),
+    }
+}
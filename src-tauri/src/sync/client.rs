@@ -0,0 +1,100 @@
+use reqwest::StatusCode;
+use sqd_core::dto::{ChangeSource, SyncReport, SyncRequest, SyncResponse};
+use sqd_core::services::{SyncService, TodoService};
+use tauri::AppHandle;
+
+use super::auth;
+use super::http::{read_json, send, SyncEndpoint};
+use crate::config::AppStorage;
+use crate::utils::{AppError, AppResult};
+
+/// 在阻塞线程池中执行数据库操作
+async fn run_blocking<T, F>(f: F) -> AppResult<T>
+where
+    F: FnOnce() -> AppResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Config(format!("Blocking task failed: {}", e)))?
+}
+
+/// 执行一次完整同步：推送本地变更、应用远端变更并推进游标
+///
+/// 只推送属于当前服务器配置（或尚未绑定）的记录，推送与拉取的记录随后绑定到该配置。
+/// 调用方需持有 `AppState::sync_lock`，保证同步期间不会切换配置。
+pub async fn sync_now(app: &AppHandle, storage: AppStorage) -> AppResult<SyncReport> {
+    let endpoint = SyncEndpoint::load(app)?;
+    let profile = endpoint.profile.clone();
+    let last_sync = {
+        let storage = storage.clone();
+        let profile = profile.clone();
+        run_blocking(move || SyncService::get_last_sync(&storage, &profile)).await?
+    };
+    let local_changes = {
+        let storage = storage.clone();
+        let profile = profile.clone();
+        let after = last_sync.clone();
+        run_blocking(move || SyncService::pending_changes(&storage, &profile, after)).await?
+    };
+
+    let pushed = local_changes.len();
+    let mut synced_ids: Vec<String> = local_changes.iter().map(|t| t.id.clone()).collect();
+    let pushed_until = local_changes.iter().map(|t| t.updated_at.clone()).max();
+    let request = SyncRequest {
+        last_sync,
+        changes: local_changes,
+    };
+
+    let url = endpoint.url("/sync");
+    let client = &endpoint.client;
+    let token = auth::access_token(app, &endpoint).await?;
+
+    let mut builder = client.post(&url).json(&request);
+    if let Some(token) = &token {
+        builder = builder.bearer_auth(token);
+    }
+    let mut response = send(builder).await?;
+
+    // access token 可能已在服务端失效（如服务重启），刷新后重试一次
+    if let (StatusCode::UNAUTHORIZED, Some(stale)) = (response.status(), &token) {
+        let token = auth::refresh(app, &endpoint, stale).await?;
+        response = send(client.post(&url).json(&request).bearer_auth(token)).await?;
+    }
+
+    let SyncResponse {
+        server_time,
+        changes,
+    } = read_json(response).await?;
+
+    let pulled_until = changes.iter().map(|t| t.updated_at.clone()).max();
+    synced_ids.extend(changes.iter().map(|t| t.id.clone()));
+    let pulled = {
+        let storage = storage.clone();
+        run_blocking(move || TodoService::upsert_batch(&storage, changes, ChangeSource::Sync)).await?
+    };
+
+    // 本次推送与拉取的最大 updated_at 之前的变更均已与服务端一致
+    let synced_until = pushed_until.max(pulled_until);
+    {
+        let server_time = server_time.clone();
+        run_blocking(move || {
+            SyncService::bind(&storage, &profile, &synced_ids)?;
+            if let Some(synced_until) = synced_until {
+                SyncService::mark_synced(&storage, &profile, &synced_until)?;
+            }
+            SyncService::set_last_sync(&storage, &profile, &server_time)
+        })
+        .await?;
+    }
+
+    tracing::info!(
+        "Sync completed ({}): pushed={}, inserted={}, updated={}, skipped={}",
+        endpoint.profile, pushed, pulled.inserted, pulled.updated, pulled.skipped
+    );
+    Ok(SyncReport {
+        pushed,
+        pulled,
+        server_time,
+    })
+}
@@ -0,0 +1,105 @@
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use super::tls;
+use crate::config::api_base::{load_active_profile, ApiProfile};
+use crate::config::constants::SYNC_TIMEOUT_SECS;
+use crate::utils::{AppError, AppResult};
+
+/// 同步服务端点：按服务器配置构建的客户端 + 基础地址
+pub struct SyncEndpoint {
+    pub client: Client,
+    pub base: String,
+    /// 服务器配置名（用于区分令牌与同步游标）
+    pub profile: String,
+}
+
+impl SyncEndpoint {
+    /// 按 `assistant_api.json` 中当前生效的配置构建端点
+    pub fn load(app: &AppHandle) -> AppResult<Self> {
+        Self::from_profile(&load_active_profile(app))
+    }
+
+    pub fn from_profile(profile: &ApiProfile) -> AppResult<Self> {
+        ensure_secure(profile)?;
+        Ok(SyncEndpoint {
+            client: build_client(profile)?,
+            base: profile.api_base.clone(),
+            profile: profile.name.clone(),
+        })
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host_str() {
+        Some(host) => {
+            host == "localhost"
+                || host
+                    .trim_matches(|c| c == '[' || c == ']')
+                    .parse::<std::net::IpAddr>()
+                    .map(|ip| ip.is_loopback())
+                    .unwrap_or(false)
+        }
+        None => false,
+    }
+}
+
+/// 拒绝明文 HTTP（本机地址除外），除非用户显式允许
+pub fn ensure_secure(config: &ApiProfile) -> AppResult<()> {
+    let url = Url::parse(&config.api_base)
+        .map_err(|e| AppError::Config(format!("Invalid api base {}: {}", config.api_base, e)))?;
+
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if config.allow_insecure_http || is_loopback(&url) => {
+            tracing::warn!("Syncing over insecure HTTP: {}", config.api_base);
+            Ok(())
+        }
+        "http" => Err(AppError::Config(
+            "拒绝使用明文 HTTP 同步，请改用 HTTPS 或在设置中显式允许不安全连接".to_string(),
+        )),
+        scheme => Err(AppError::Config(format!("Unsupported scheme: {}", scheme))),
+    }
+}
+
+/// 构建同步用的 HTTP 客户端（自定义 CA / 证书指纹）
+fn build_client(config: &ApiProfile) -> AppResult<Client> {
+    let mut builder = Client::builder().timeout(Duration::from_secs(SYNC_TIMEOUT_SECS));
+    if let Some(tls) = tls::client_config(config)? {
+        builder = builder.use_preconfigured_tls(tls);
+    }
+    builder
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to build http client: {}", e)))
+}
+
+/// 发送请求，网络错误统一转换为 `AppError::Network`
+pub async fn send(request: RequestBuilder) -> AppResult<Response> {
+    request
+        .send()
+        .await
+        .map_err(|e| AppError::Network(format!("Request failed: {}", e)))
+}
+
+/// 检查状态码并解析 JSON 响应体
+pub async fn read_json<T: DeserializeOwned>(response: Response) -> AppResult<T> {
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(AppError::Unauthorized("登录已失效，请重新登录".to_string()));
+    }
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(AppError::Network(format!("{} {}", status, text)));
+    }
+
+    response
+        .json::<T>()
+        .await
+        .map_err(|e| AppError::Network(format!("Invalid response body: {}", e)))
+}
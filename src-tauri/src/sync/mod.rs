@@ -0,0 +1,6 @@
+pub mod auth;
+pub mod client;
+pub mod http;
+pub mod probe;
+pub mod tls;
+pub mod token_store;
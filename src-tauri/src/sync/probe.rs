@@ -0,0 +1,107 @@
+use reqwest::{StatusCode, Url};
+use serde::Serialize;
+use sqd_core::dto::HealthResponse;
+use std::time::{Duration, Instant};
+
+use super::http::{ensure_secure, send, SyncEndpoint};
+use crate::config::api_base::ApiProfile;
+use crate::config::constants::PROBE_TIMEOUT_SECS;
+use crate::utils::{AppError, AppResult};
+
+/// 连通性探测结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeResult {
+    pub api_base: String,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub server_version: Option<String>,
+    /// 服务端未返回该信息时为 None
+    pub auth_required: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// 校验地址格式：http/https、必须有主机、不允许账号密码 / 查询参数 / 片段
+pub fn validate_api_base(value: &str) -> AppResult<String> {
+    let normalized = value.trim().trim_end_matches('/').to_string();
+    let url = Url::parse(&normalized)
+        .map_err(|e| AppError::Validation(format!("无效的服务器地址: {}", e)))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(AppError::Validation("服务器地址必须以 http:// 或 https:// 开头".to_string()));
+    }
+    if url.host_str().map(|h| h.is_empty()).unwrap_or(true) {
+        return Err(AppError::Validation("服务器地址缺少主机名".to_string()));
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(AppError::Validation("服务器地址不能包含账号密码".to_string()));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(AppError::Validation("服务器地址不能包含查询参数或锚点".to_string()));
+    }
+
+    Ok(normalized)
+}
+
+/// 请求 `{api_base}/health`，在超时内返回结构化结果（探测失败不返回 Err）
+///
+/// 被明文 HTTP 策略拒绝的地址不发请求，作为探测失败返回。
+pub async fn probe(config: &ApiProfile) -> AppResult<ProbeResult> {
+    let mut result = ProbeResult {
+        api_base: config.api_base.clone(),
+        reachable: false,
+        latency_ms: None,
+        server_version: None,
+        auth_required: None,
+        error: None,
+    };
+    if ensure_secure(config).is_err() {
+        let scheme = Url::parse(&config.api_base)
+            .map(|url| url.scheme().to_string())
+            .unwrap_or_default();
+        tracing::warn!("Refusing to probe {} over insecure {}", config.api_base, scheme);
+        result.error = Some(format!(
+            "不安全的 {}:// 地址：请改用 HTTPS 或在设置中显式允许不安全连接",
+            scheme
+        ));
+        return Ok(result);
+    }
+    let endpoint = SyncEndpoint::from_profile(config)?;
+
+    let started = Instant::now();
+    let request = endpoint
+        .client
+        .get(endpoint.url("/health"))
+        .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS));
+    let response = match send(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return Ok(result);
+        }
+    };
+    result.latency_ms = Some(started.elapsed().as_millis() as u64);
+
+    let status = response.status();
+    if status.is_success() {
+        match response.json::<HealthResponse>().await {
+            Ok(health) => {
+                result.reachable = true;
+                result.server_version = Some(health.version);
+                result.auth_required = Some(health.auth_required);
+            }
+            Err(e) => result.error = Some(format!("Invalid health response: {}", e)),
+        }
+    } else if status == StatusCode::UNAUTHORIZED {
+        result.reachable = true;
+        result.auth_required = Some(true);
+    } else {
+        result.error = Some(format!("Health check returned {}", status));
+    }
+
+    tracing::info!(
+        "Probed {}: reachable={}, latency={:?}ms, version={:?}",
+        result.api_base, result.reachable, result.latency_ms, result.server_version
+    );
+    Ok(result)
+}
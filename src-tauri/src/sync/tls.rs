@@ -0,0 +1,159 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::config::api_base::ApiProfile;
+use crate::utils::{AppError, AppResult};
+
+/// 解析 SPKI 指纹：base64 编码的 SHA-256，可带 `sha256/` 前缀
+pub fn parse_spki_pin(pin: &str) -> AppResult<[u8; 32]> {
+    let encoded = pin.trim().trim_start_matches("sha256/");
+    let bytes = STANDARD
+        .decode(encoded)
+        .map_err(|_| AppError::Validation(format!("无效的证书指纹: {}", pin)))?;
+    bytes
+        .try_into()
+        .map_err(|_| AppError::Validation(format!("证书指纹必须是 SHA-256（32 字节）: {}", pin)))
+}
+
+/// 读取 PEM 格式的 CA 证书
+pub fn load_ca_bundle(path: &str) -> AppResult<Vec<CertificateDer<'static>>> {
+    let file = File::open(path)
+        .map_err(|e| AppError::Config(format!("Failed to open CA bundle {}: {}", path, e)))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Config(format!("Failed to parse CA bundle {}: {}", path, e)))?;
+    if certs.is_empty() {
+        return Err(AppError::Config(format!("CA bundle {} contains no certificates", path)));
+    }
+    Ok(certs)
+}
+
+fn spki_sha256(cert: &CertificateDer<'_>) -> Option<[u8; 32]> {
+    let (_, parsed) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    let digest = Sha256::digest(parsed.public_key().raw);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&digest);
+    Some(hash)
+}
+
+/// 在标准证书链校验之后再校验 SPKI 指纹
+#[derive(Debug)]
+struct PinnedVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<[u8; 32]>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        let matched = std::iter::once(end_entity)
+            .chain(intermediates.iter())
+            .filter_map(spki_sha256)
+            .any(|hash| self.pins.contains(&hash));
+        if matched {
+            Ok(verified)
+        } else {
+            tracing::error!("Certificate pin mismatch for {:?}", server_name);
+            Err(rustls::Error::General("certificate pin mismatch".to_string()))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// 保存前校验 CA 文件与证书指纹格式（空值忽略）
+pub fn validate_options(ca_bundle: Option<&str>, spki_pins: &[String]) -> AppResult<()> {
+    if let Some(path) = ca_bundle.map(str::trim).filter(|s| !s.is_empty()) {
+        load_ca_bundle(path)?;
+    }
+    for pin in spki_pins.iter().filter(|s| !s.trim().is_empty()) {
+        parse_spki_pin(pin)?;
+    }
+    Ok(())
+}
+
+/// 根据配置构建 rustls 客户端配置；未配置 CA 与指纹时返回 None（使用 reqwest 默认）
+pub fn client_config(config: &ApiProfile) -> AppResult<Option<ClientConfig>> {
+    if config.ca_bundle.is_none() && config.spki_pins.is_empty() {
+        return Ok(None);
+    }
+
+    let mut roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    if let Some(path) = &config.ca_bundle {
+        for cert in load_ca_bundle(path)? {
+            roots
+                .add(cert)
+                .map_err(|e| AppError::Config(format!("Invalid CA certificate: {}", e)))?;
+        }
+    }
+
+    let provider: Arc<CryptoProvider> = Arc::new(ring::default_provider());
+    let webpki = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| AppError::Config(format!("Failed to build certificate verifier: {}", e)))?;
+
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_protocol_versions(rustls::DEFAULT_VERSIONS)
+        .map_err(|e| AppError::Config(format!("Failed to configure TLS: {}", e)))?;
+
+    let tls = if config.spki_pins.is_empty() {
+        builder.with_webpki_verifier(webpki).with_no_client_auth()
+    } else {
+        let pins = config
+            .spki_pins
+            .iter()
+            .map(|pin| parse_spki_pin(pin))
+            .collect::<AppResult<Vec<_>>>()?;
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(PinnedVerifier { inner: webpki, pins }))
+            .with_no_client_auth()
+    };
+    Ok(Some(tls))
+}
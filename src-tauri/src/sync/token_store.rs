@@ -0,0 +1,127 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::config::api_base::DEFAULT_PROFILE;
+use crate::config::paths::config_file_path;
+use crate::utils::{AppError, AppResult};
+
+/// 旧版（单服务器）令牌文件，迁移为 default 配置的令牌
+const LEGACY_TOKEN_FILE: &str = "assistant_auth.enc";
+/// 加密密钥保存在系统钥匙串中，与密文分离
+const KEYRING_SERVICE: &str = "com.shengwenwang.tauri-app";
+const KEYRING_USER: &str = "sync-token-key";
+const NONCE_LEN: usize = 12;
+
+/// 持久化的登录令牌
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredTokens {
+    pub username: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// access token 过期时间（ISO 8601 UTC）
+    pub expires_at: String,
+}
+
+/// 每个服务器配置一个加密令牌文件（配置目录下）
+fn token_path(app: &AppHandle, profile: &str) -> AppResult<PathBuf> {
+    let path = config_file_path(app, &format!("assistant_auth_{}.enc", profile))?;
+    if profile == DEFAULT_PROFILE && !path.exists() {
+        let legacy = config_file_path(app, LEGACY_TOKEN_FILE)?;
+        if legacy.exists() {
+            fs::rename(&legacy, &path)
+                .map_err(|e| AppError::Config(format!("Failed to migrate token file: {}", e)))?;
+        }
+    }
+    Ok(path)
+}
+
+fn encryption_key() -> AppResult<Key<Aes256Gcm>> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| AppError::Config(format!("Failed to open keyring: {}", e)))?;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = STANDARD
+                .decode(encoded)
+                .map_err(|e| AppError::Config(format!("Invalid token key: {}", e)))?;
+            if bytes.len() != 32 {
+                return Err(AppError::Config("Invalid token key length".to_string()));
+            }
+            Ok(*Key::<Aes256Gcm>::from_slice(&bytes))
+        }
+        Err(keyring::Error::NoEntry) => {
+            let key = Aes256Gcm::generate_key(&mut OsRng);
+            entry
+                .set_password(&STANDARD.encode(key))
+                .map_err(|e| AppError::Config(format!("Failed to store token key: {}", e)))?;
+            tracing::info!("Generated new token encryption key");
+            Ok(key)
+        }
+        Err(e) => Err(AppError::Config(format!("Failed to read keyring: {}", e))),
+    }
+}
+
+/// 读取并解密令牌，未登录时返回 None
+pub fn load_tokens(app: &AppHandle, profile: &str) -> AppResult<Option<StoredTokens>> {
+    let path = token_path(app, profile)?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Ok(None),
+    };
+
+    let bytes = STANDARD
+        .decode(content.trim())
+        .map_err(|e| AppError::Config(format!("Corrupted token file: {}", e)))?;
+    if bytes.len() <= NONCE_LEN {
+        return Err(AppError::Config("Corrupted token file".to_string()));
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(&encryption_key()?);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::Config("Failed to decrypt token file".to_string()))?;
+
+    let tokens = serde_json::from_slice(&plaintext)
+        .map_err(|e| AppError::Config(format!("Failed to parse token file: {}", e)))?;
+    Ok(Some(tokens))
+}
+
+/// 加密并保存令牌
+pub fn save_tokens(app: &AppHandle, profile: &str, tokens: &StoredTokens) -> AppResult<()> {
+    let path = token_path(app, profile)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| AppError::Config(format!("Failed to create config dir: {}", e)))?;
+    }
+
+    let plaintext = serde_json::to_vec(tokens)
+        .map_err(|e| AppError::Config(format!("Failed to serialize tokens: {}", e)))?;
+    let cipher = Aes256Gcm::new(&encryption_key()?);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_ref())
+        .map_err(|_| AppError::Config("Failed to encrypt tokens".to_string()))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    fs::write(&path, STANDARD.encode(payload))
+        .map_err(|e| AppError::Config(format!("Failed to write token file: {}", e)))?;
+    Ok(())
+}
+
+/// 删除本地令牌
+pub fn clear_tokens(app: &AppHandle, profile: &str) -> AppResult<()> {
+    let path = token_path(app, profile)?;
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|e| AppError::Config(format!("Failed to remove token file: {}", e)))?;
+    }
+    Ok(())
+}
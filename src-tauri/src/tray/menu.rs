@@ -0,0 +1,174 @@
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItem, SubmenuBuilder};
+use tauri::tray::TrayIconBuilder;
+use tauri::{App, AppHandle, Manager, Wry};
+
+use crate::config::constants::{TRAY_MAX_TASKS, TRAY_TASK_LABEL_CHARS};
+use crate::config::AppState;
+use crate::db::Todo;
+use crate::dto::UpdateTodoInput;
+use crate::services::TodoService;
+use crate::shortcuts::actions::request_complete;
+use crate::window::{create_or_show_quick_add_window, create_or_show_stats_window, show_main_window};
+
+const TRAY_ID: &str = "main";
+const TRAY_TITLE: &str = "SQD 工作助手";
+/// 任务子菜单项 id 前缀，后接任务 id
+const COMPLETE_PREFIX: &str = "todo-complete:";
+const PENDING_PREFIX: &str = "todo-pending:";
+
+/// 托盘需要的任务数据：进行中任务（按更新时间倒序）与待办数量
+struct TraySnapshot {
+    in_progress: Vec<Todo>,
+    pending_count: usize,
+}
+
+fn load_snapshot(app: &AppHandle) -> TraySnapshot {
+    let todos = match app.try_state::<AppState>() {
+        Some(state) => TodoService::get_all(&state.pool()).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load todos for tray: {}", e);
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    let pending_count = todos.iter().filter(|t| t.status == "pending").count();
+    let in_progress = todos.into_iter().filter(|t| t.status == "in_progress").collect();
+    TraySnapshot { in_progress, pending_count }
+}
+
+fn task_label(todo: &Todo) -> String {
+    let label = format!("{} · {}", todo.title, todo.broker);
+    if label.chars().count() > TRAY_TASK_LABEL_CHARS {
+        let truncated: String = label.chars().take(TRAY_TASK_LABEL_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        label
+    }
+}
+
+fn tooltip(snapshot: &TraySnapshot) -> String {
+    format!(
+        "{}\n待办 {} · 进行中 {}",
+        TRAY_TITLE,
+        snapshot.pending_count,
+        snapshot.in_progress.len()
+    )
+}
+
+fn build_menu(app: &AppHandle, snapshot: &TraySnapshot) -> tauri::Result<Menu<Wry>> {
+    let mut builder = MenuBuilder::new(app);
+
+    if snapshot.in_progress.is_empty() {
+        builder = builder.item(&MenuItem::with_id(app, "no_tasks", "暂无进行中的任务", false, None::<&str>)?);
+    }
+    for todo in snapshot.in_progress.iter().take(TRAY_MAX_TASKS) {
+        let submenu = SubmenuBuilder::new(app, task_label(todo))
+            .text(format!("{}{}", COMPLETE_PREFIX, todo.id), "完成…")
+            .text(format!("{}{}", PENDING_PREFIX, todo.id), "退回待办")
+            .build()?;
+        builder = builder.item(&submenu);
+    }
+    if snapshot.in_progress.len() > TRAY_MAX_TASKS {
+        let more = format!("还有 {} 个进行中的任务", snapshot.in_progress.len() - TRAY_MAX_TASKS);
+        builder = builder.item(&MenuItem::with_id(app, "more_tasks", more, false, None::<&str>)?);
+    }
+
+    builder
+        .separator()
+        .text("show", "显示主窗口")
+        .text("stats", "数据统计")
+        .text("quick_add", "快速添加")
+        .separator()
+        .text("quit", "退出")
+        .build()
+}
+
+/// 创建托盘图标
+pub fn create_tray(app: &App) -> tauri::Result<()> {
+    let handle = app.handle();
+    let snapshot = load_snapshot(handle);
+    let menu = build_menu(handle, &snapshot)?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(app.default_window_icon().unwrap().clone())
+        .menu(&menu)
+        .tooltip(tooltip(&snapshot))
+        .on_menu_event(handle_menu_event)
+        .build(app)?;
+    Ok(())
+}
+
+/// 按当前任务重建托盘菜单与提示
+pub fn refresh_tray(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let snapshot = load_snapshot(app);
+    match build_menu(app, &snapshot) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                tracing::warn!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to build tray menu: {}", e),
+    }
+    let _ = tray.set_tooltip(Some(tooltip(&snapshot)));
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id.as_ref();
+    match id {
+        "show" => {
+            tracing::info!("Tray menu: Show main window");
+            show_main_window(app);
+        }
+        "stats" => {
+            tracing::info!("Tray menu: Stats view");
+            create_or_show_stats_window(app);
+        }
+        "quick_add" => {
+            tracing::info!("Tray menu: Quick add");
+            create_or_show_quick_add_window(app);
+        }
+        "quit" => {
+            tracing::info!("Tray menu: Quit");
+            app.exit(0);
+        }
+        _ => {
+            if let Some(todo_id) = id.strip_prefix(COMPLETE_PREFIX) {
+                complete_todo(app, todo_id);
+            } else if let Some(todo_id) = id.strip_prefix(PENDING_PREFIX) {
+                reset_to_pending(app, todo_id);
+            }
+        }
+    }
+}
+
+/// 完成需要填写结论，交给主窗口的结论对话框
+fn complete_todo(app: &AppHandle, todo_id: &str) {
+    tracing::info!("Tray menu: Complete todo {}", todo_id);
+    let storage = app.state::<AppState>().storage();
+    match TodoService::get(&storage, todo_id) {
+        Ok(Some(todo)) => request_complete(app, &todo),
+        // 菜单过期（任务已被删除），重建菜单
+        Ok(None) => refresh_tray(app),
+        Err(e) => tracing::error!("Failed to load todo {}: {}", todo_id, e),
+    }
+}
+
+fn reset_to_pending(app: &AppHandle, todo_id: &str) {
+    tracing::info!("Tray menu: Reset todo {} to pending", todo_id);
+    let storage = app.state::<AppState>().storage();
+    let input = UpdateTodoInput {
+        todo_id: todo_id.to_string(),
+        title: None,
+        status: Some("pending".to_string()),
+        broker: None,
+        conclusion: None,
+        custom_fields: None,
+        // 托盘菜单随每次变更重建，操作以菜单为准，不做版本校验
+        expected_version: None,
+    };
+    if let Err(e) = TodoService::update(&storage, input) {
+        tracing::error!("Failed to reset todo {}: {}", todo_id, e);
+    }
+}
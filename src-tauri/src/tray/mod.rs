@@ -0,0 +1,3 @@
+pub mod menu;
+
+pub use menu::{create_tray, refresh_tray};
@@ -1,27 +0,0 @@
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-pub enum AppError {
-    #[error("Database error: {0}")]
-    Database(#[from] diesel::result::Error),
-
-    #[error("Connection pool error: {0}")]
-    PoolError(String),
-
-    #[error("Validation error: {0}")]
-    Validation(String),
-
-    #[error("Config error: {0}")]
-    Config(String),
-}
-
-impl serde::Serialize for AppError {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(&self.to_string())
-    }
-}
-
-pub type AppResult<T> = Result<T, AppError>;
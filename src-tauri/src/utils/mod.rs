@@ -1,7 +1,4 @@
-pub mod error;
 pub mod logger;
-pub mod validation;
 
-pub use error::{AppError, AppResult};
 pub use logger::init_logger;
-pub use validation::{escape_like_pattern, TodoInput};
+pub use sqd_core::utils::{AppError, AppResult};
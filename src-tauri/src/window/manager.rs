@@ -45,3 +45,25 @@ pub fn create_or_show_stats_window(app: &AppHandle) {
         .build();
     }
 }
+
+/// 显示并聚焦主窗口
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+        let _ = window.unminimize();
+    }
+}
+
+/// 主窗口可见且有焦点时隐藏，否则显示
+pub fn toggle_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let visible = window.is_visible().unwrap_or(false);
+        let focused = window.is_focused().unwrap_or(false);
+        if visible && focused {
+            let _ = window.hide();
+        } else {
+            show_main_window(app);
+        }
+    }
+}
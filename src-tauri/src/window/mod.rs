@@ -1,3 +1,6 @@
 pub mod manager;
 
-pub use manager::{create_or_show_quick_add_window, create_or_show_stats_window};
+pub use manager::{
+    create_or_show_quick_add_window, create_or_show_stats_window, show_main_window,
+    toggle_main_window,
+};
@@ -0,0 +1,20 @@
+import type { AuthStatus } from '../types/auth';
+import { tauri } from './client';
+
+/**
+ * 同步账号 API（Tauri）
+ * 令牌由后端加密保存，前端只能拿到登录状态
+ */
+export const authApi = {
+  login: (username: string, password: string) => {
+    return tauri.invoke<AuthStatus>('login', { username, password });
+  },
+
+  logout: () => {
+    return tauri.invoke<void>('logout');
+  },
+
+  getStatus: () => {
+    return tauri.invoke<AuthStatus>('get_auth_status');
+  },
+};
@@ -32,6 +32,10 @@ async function request<T>(method: HttpMethod, path: string, body?: unknown): Pro
   }
 
   if (!response.ok) {
+    // 冲突响应体为 { kind: 'conflict', message, current }，与 Tauri 命令的错误形式一致
+    if (response.status === 409 && response.headers.get('Content-Type')?.includes('application/json')) {
+      throw await response.json();
+    }
     const text = await response.text();
     throw new Error(text || `Request failed: ${response.status} ${response.statusText} (url: ${url})`);
   }
@@ -0,0 +1,30 @@
+import type { Settings, SettingsPatch, ShortcutAction, ShortcutStatus } from '../types/settings';
+import { tauri } from './client';
+
+/**
+ * 用户设置 API（Tauri）
+ * 修改成功后后端会向所有窗口广播 `settings-changed`
+ */
+export const settingsApi = {
+  get: () => {
+    return tauri.invoke<Settings>('get_settings');
+  },
+
+  update: (patch: SettingsPatch) => {
+    return tauri.invoke<Settings>('update_settings', { patch });
+  },
+
+  /**
+   * 全局快捷键注册状态（被其他应用占用时 registered 为 false）
+   */
+  getShortcutStatus: () => {
+    return tauri.invoke<ShortcutStatus[]>('get_shortcut_status');
+  },
+
+  /**
+   * 可绑定快捷键的动作（id 用作 `shortcuts` 的键）
+   */
+  listShortcutActions: () => {
+    return tauri.invoke<ShortcutAction[]>('list_shortcut_actions');
+  },
+};
@@ -1,4 +1,4 @@
-import type { Todo, CreateTodoInput, UpdateTodoInput, TodoStatus } from '../types/todo';
+import type { Todo, CreateTodoInput, UpdateTodoInput, TodoStatus, UpsertSummary, PurgeReport, EmptyTrashReport, SyncReport, TodoHistoryPage, AuditExportInput, TodoPatch, BulkItemResult, TodoSeries, CreateSeriesInput, UpdateSeriesInput, TodoTemplate, TemplateInput, Checklist, TodoDetail, LinkKind, Tag, UpdateTagInput, CustomField, FieldType, FieldFilter } from '../types/todo';
 import { http, isTauri, tauri } from './client';
 
 /**
@@ -9,9 +9,9 @@ export const todoApi = {
   /**
    * 获取所有 Todo
    */
-  getAll: () => {
+  getAll: (tagIds: string[] = [], fieldFilter: FieldFilter | null = null) => {
     if (isTauri()) {
-      return tauri.invoke<Todo[]>('get_todos');
+      return tauri.invoke<Todo[]>('get_todos', { tagIds, fieldFilter });
     }
     return http.get<Todo[]>('/todos');
   },
@@ -25,10 +25,11 @@ export const todoApi = {
       status: input.status,
       broker: input.broker.trim(),
       conclusion: input.conclusion || null,
+      customFields: input.customFields ?? null,
     };
 
     if (isTauri()) {
-      return tauri.invoke<Todo>('create_todo', payload);
+      return tauri.invoke<Todo>('create_todo', { ...payload, source: input.source ?? null });
     }
     return http.post<Todo>('/todos', payload);
   },
@@ -43,6 +44,8 @@ export const todoApi = {
       status?: TodoStatus;
       broker?: string;
       conclusion?: string | null;
+      customFields?: Record<string, string | number | null>;
+      expectedVersion?: number;
     } = { todoId: id };
 
     if (input.title !== undefined) {
@@ -57,12 +60,44 @@ export const todoApi = {
     if (input.conclusion !== undefined) {
       payload.conclusion = input.conclusion || null;
     }
+    if (input.customFields !== undefined) {
+      payload.customFields = input.customFields;
+    }
+    if (input.expectedVersion !== undefined) {
+      payload.expectedVersion = input.expectedVersion;
+    }
 
     if (isTauri()) {
       return tauri.invoke<Todo>('update_todo', { input: payload });
     }
-    const { todoId, ...rest } = payload;
-    return http.patch<Todo>(`/todos/${todoId}`, rest);
+    const { todoId, expectedVersion, customFields, ...rest } = payload;
+    return http.patch<Todo>(`/todos/${todoId}`, {
+      ...rest,
+      custom_fields: customFields,
+      expected_version: expectedVersion,
+    });
+  },
+
+  /**
+   * 批量更新 Todo，返回逐个结果
+   */
+  bulkUpdate: (ids: string[], patch: TodoPatch) => {
+    const input = { todoIds: ids, patch };
+    if (isTauri()) {
+      return tauri.invoke<BulkItemResult[]>('bulk_update_todos', { input });
+    }
+    return http.post<BulkItemResult[]>('/todos/bulk-update', input);
+  },
+
+  /**
+   * 批量删除 Todo，返回逐个结果
+   */
+  bulkDelete: (ids: string[]) => {
+    const input = { todoIds: ids };
+    if (isTauri()) {
+      return tauri.invoke<BulkItemResult[]>('bulk_delete_todos', { input });
+    }
+    return http.post<BulkItemResult[]>('/todos/bulk-delete', input);
   },
 
   /**
@@ -77,12 +112,295 @@ export const todoApi = {
     return http.delete(`/todos/${id}`);
   },
 
+  /**
+   * 获取回收站列表（Tauri）
+   */
+  listDeleted: () => {
+    return tauri.invoke<Todo[]>('list_deleted_todos');
+  },
+
+  /**
+   * 从回收站恢复（Tauri）
+   */
+  restore: (id: string) => {
+    return tauri.invoke<Todo>('restore_todo', {
+      input: { todoId: id }
+    });
+  },
+
+  /**
+   * 撤销本次运行中最近一次任务操作，无可撤销时返回 null（Tauri）
+   */
+  undo: () => {
+    return tauri.invoke<Todo | null>('undo_todo');
+  },
+
+  /**
+   * 重做最近一次撤销的任务操作，无可重做时返回 null（Tauri）
+   */
+  redo: () => {
+    return tauri.invoke<Todo | null>('redo_todo');
+  },
+
+  /**
+   * 分页获取任务的变更历史（Tauri）
+   */
+  getHistory: (id: string, page = 1, pageSize = 20) => {
+    return tauri.invoke<TodoHistoryPage>('get_todo_history', {
+      input: { todoId: id, page, pageSize }
+    });
+  },
+
+  /**
+   * 导出审计日志为 CSV 文本（Tauri）
+   */
+  exportAudit: (input: AuditExportInput = {}) => {
+    return tauri.invoke<string>('export_todo_audit', { input });
+  },
+
+  /**
+   * 创建重复任务系列，返回第一个实例（Tauri）
+   */
+  createSeries: (input: CreateSeriesInput) => {
+    return tauri.invoke<Todo>('create_todo_series', {
+      input: { ...input, title: input.title.trim(), broker: input.broker.trim() }
+    });
+  },
+
+  /**
+   * 获取所有重复任务系列（Tauri）
+   */
+  listSeries: () => {
+    return tauri.invoke<TodoSeries[]>('list_todo_series');
+  },
+
+  /**
+   * 修改重复任务系列（Tauri）
+   */
+  updateSeries: (seriesId: string, input: UpdateSeriesInput) => {
+    return tauri.invoke<TodoSeries>('update_todo_series', {
+      input: { seriesId, ...input }
+    });
+  },
+
+  /**
+   * 停止重复任务系列，deleteOpen 为 true 时同时删除未完成的实例（Tauri）
+   */
+  stopSeries: (seriesId: string, deleteOpen = false) => {
+    return tauri.invoke<TodoSeries>('stop_todo_series', {
+      input: { seriesId, deleteOpen }
+    });
+  },
+
+  /**
+   * 获取所有任务模板（Tauri）
+   */
+  listTemplates: () => {
+    return tauri.invoke<TodoTemplate[]>('list_todo_templates');
+  },
+
+  /**
+   * 创建任务模板（Tauri）
+   */
+  createTemplate: (input: TemplateInput) => {
+    return tauri.invoke<TodoTemplate>('create_todo_template', { input });
+  },
+
+  /**
+   * 修改任务模板（Tauri）
+   */
+  updateTemplate: (templateId: string, input: Partial<TemplateInput>) => {
+    return tauri.invoke<TodoTemplate>('update_todo_template', {
+      input: { templateId, ...input }
+    });
+  },
+
+  /**
+   * 删除任务模板（Tauri）
+   */
+  deleteTemplate: (templateId: string) => {
+    return tauri.invoke('delete_todo_template', { input: { templateId } });
+  },
+
+  /**
+   * 按模板为券商批量创建任务（Tauri）
+   */
+  instantiateTemplate: (templateId: string, broker: string) => {
+    return tauri.invoke<Todo[]>('instantiate_template', { templateId, broker: broker.trim() });
+  },
+
+  /**
+   * 导出所有任务模板为 JSON 文本（Tauri）
+   */
+  exportTemplates: () => {
+    return tauri.invoke<string>('export_todo_templates');
+  },
+
+  /**
+   * 获取任务的检查项（Tauri）
+   */
+  getItems: (todoId: string) => {
+    return tauri.invoke<Checklist>('get_todo_items', { todoId });
+  },
+
+  /**
+   * 添加检查项到末尾（Tauri）
+   */
+  addItem: (todoId: string, content: string) => {
+    return tauri.invoke<Checklist>('add_todo_item', {
+      input: { todoId, content: content.trim() }
+    });
+  },
+
+  /**
+   * 修改检查项内容或勾选状态（Tauri）
+   */
+  updateItem: (itemId: string, changes: { content?: string; done?: boolean }) => {
+    return tauri.invoke<Checklist>('update_todo_item', {
+      input: { itemId, ...changes }
+    });
+  },
+
+  /**
+   * 删除检查项（Tauri）
+   */
+  deleteItem: (itemId: string) => {
+    return tauri.invoke<Checklist>('delete_todo_item', { input: { itemId } });
+  },
+
+  /**
+   * 按给定顺序重新排列检查项（Tauri）
+   */
+  reorderItems: (todoId: string, itemIds: string[]) => {
+    return tauri.invoke<Checklist>('reorder_todo_items', {
+      input: { todoId, itemIds }
+    });
+  },
+
+  /**
+   * 获取任务详情：检查项与关联任务（Tauri）
+   */
+  getDetail: (todoId: string) => {
+    return tauri.invoke<TodoDetail>('get_todo_detail', { todoId });
+  },
+
+  /**
+   * 创建任务关联，kind 从 todoId 一端看（Tauri）
+   */
+  createLink: (todoId: string, otherId: string, kind: LinkKind) => {
+    return tauri.invoke<TodoDetail>('create_todo_link', {
+      input: { todoId, otherId, kind }
+    });
+  },
+
+  /**
+   * 删除任务关联（Tauri）
+   */
+  deleteLink: (linkId: number) => {
+    return tauri.invoke('delete_todo_link', { input: { linkId } });
+  },
+
+  /**
+   * 获取所有标签及任务数（Tauri）
+   */
+  listTags: () => {
+    return tauri.invoke<Tag[]>('list_tags');
+  },
+
+  /**
+   * 创建标签（Tauri）
+   */
+  createTag: (name: string, color?: string | null) => {
+    return tauri.invoke('create_tag', {
+      input: { name: name.trim(), color: color || null }
+    });
+  },
+
+  /**
+   * 重命名标签或修改颜色（Tauri）
+   */
+  updateTag: (tagId: string, input: UpdateTagInput) => {
+    return tauri.invoke('update_tag', { input: { tagId, ...input } });
+  },
+
+  /**
+   * 删除标签（Tauri）
+   */
+  deleteTag: (tagId: string) => {
+    return tauri.invoke('delete_tag', { input: { tagId } });
+  },
+
+  /**
+   * 把源标签合并到目标标签（Tauri）
+   */
+  mergeTags: (sourceIds: string[], targetId: string) => {
+    return tauri.invoke('merge_tags', { input: { sourceIds, targetId } });
+  },
+
+  /**
+   * 设置任务的全部标签（Tauri）
+   */
+  setTodoTags: (todoId: string, tagIds: string[]) => {
+    return tauri.invoke<string[]>('set_todo_tags', { input: { todoId, tagIds } });
+  },
+
+  /**
+   * 获取所有任务的标签：任务 id → 标签 id（Tauri）
+   */
+  getTodoTagMap: () => {
+    return tauri.invoke<Record<string, string[]>>('get_todo_tag_map');
+  },
+
+  /**
+   * 获取自定义字段定义（Tauri）
+   */
+  listCustomFields: () => {
+    return tauri.invoke<CustomField[]>('list_custom_fields');
+  },
+
+  /**
+   * 创建自定义字段（Tauri）
+   */
+  createCustomField: (key: string, name: string, fieldType: FieldType, options: string[] = []) => {
+    return tauri.invoke<CustomField>('create_custom_field', {
+      input: { key: key.trim(), name: name.trim(), fieldType, options }
+    });
+  },
+
+  /**
+   * 修改自定义字段的名称或选项（Tauri）
+   */
+  updateCustomField: (fieldId: string, input: { name?: string; options?: string[] }) => {
+    return tauri.invoke<CustomField>('update_custom_field', { input: { fieldId, ...input } });
+  },
+
+  /**
+   * 删除自定义字段，任务中的值保留（Tauri）
+   */
+  deleteCustomField: (fieldId: string) => {
+    return tauri.invoke('delete_custom_field', { input: { fieldId } });
+  },
+
+  /**
+   * 按给定顺序重排自定义字段（Tauri）
+   */
+  reorderCustomFields: (fieldIds: string[]) => {
+    return tauri.invoke<CustomField[]>('reorder_custom_fields', { input: { fieldIds } });
+  },
+
+  /**
+   * 清空回收站（Tauri）
+   */
+  emptyTrash: () => {
+    return tauri.invoke<EmptyTrashReport>('empty_trash');
+  },
+
   /**
    * 搜索 Todo
    */
-  search: (query: string) => {
+  search: (query: string, tagIds: string[] = [], fieldFilter: FieldFilter | null = null) => {
     if (isTauri()) {
-      return tauri.invoke<Todo[]>('search_todos', { query });
+      return tauri.invoke<Todo[]>('search_todos', { query, tagIds, fieldFilter });
     }
     return http.get<Todo[]>(`/todos?query=${encodeURIComponent(query)}`);
   },
@@ -100,13 +418,20 @@ export const todoApi = {
    * 批量应用远端变更（Tauri）
    */
   applyRemoteChanges: (todos: Todo[]) => {
-    return tauri.invoke<void>('upsert_todos', { todos });
+    return tauri.invoke<UpsertSummary>('upsert_todos', { todos });
+  },
+
+  /**
+   * 清理已同步且超过保留期的软删除记录（Tauri）
+   */
+  purgeDeleted: (retentionDays?: number) => {
+    return tauri.invoke<PurgeReport>('purge_deleted', { retentionDays: retentionDays ?? null });
   },
 
   /**
-   * 与远端同步（Web 或直接 HTTP）
+   * 与远端同步（Tauri，由后端携带登录令牌发起请求）
    */
-  syncWithServer: (payload: { last_sync: string | null; changes: Todo[] }) => {
-    return http.post<{ server_time: string; changes: Todo[] }>('/sync', payload);
+  syncNow: () => {
+    return tauri.invoke<SyncReport>('sync_now');
   }
 };
@@ -0,0 +1,28 @@
+import type { Workspace, WorkspaceConfig } from '../types/workspace';
+import { tauri } from './client';
+
+/**
+ * 本地工作区 API（Tauri）
+ * 每个工作区有独立的数据库和服务器配置，切换后后端会发出 `workspace-changed`
+ */
+export const workspaceApi = {
+  list: () => {
+    return tauri.invoke<WorkspaceConfig>('list_workspaces');
+  },
+
+  create: (name: string, profile?: string) => {
+    return tauri.invoke<Workspace>('create_workspace', { input: { name, profile: profile ?? null } });
+  },
+
+  rename: (id: string, name: string) => {
+    return tauri.invoke<WorkspaceConfig>('rename_workspace', { id, name });
+  },
+
+  switch: (id: string) => {
+    return tauri.invoke<Workspace>('switch_workspace', { id });
+  },
+
+  remove: (id: string) => {
+    return tauri.invoke<WorkspaceConfig>('delete_workspace', { id });
+  },
+};
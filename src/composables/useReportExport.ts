@@ -1,16 +1,24 @@
-import { type Ref } from 'vue';
-import type { Todo } from '../types/todo';
+import { ref, type Ref } from 'vue';
+import type { CustomField, Todo } from '../types/todo';
 import { formatDate, formatDateRange } from '../utils/dateUtils';
 import { logger } from '../utils/logger';
+import { formatFieldValues } from '../utils/todo';
 
 export function useReportExport(
   _reportTitle: Ref<string>,
   reportTimeRange: Ref<'daily' | 'weekly' | 'custom'>,
   reportTodos: Ref<Todo[]>,
   reportBrokerStats: Ref<Record<string, Todo[]>>,
-  getReportDateRange: () => [Date, Date]
+  getReportDateRange: () => [Date, Date],
+  customFields: Ref<CustomField[]> = ref([])
 ) {
 
+  // 任务标题后附加已填写的自定义字段
+  function describeTodo(todo: Todo): string {
+    const fields = formatFieldValues(todo, customFields.value);
+    return fields ? `${todo.title}（${fields}）` : todo.title;
+  }
+
   function generateMarkdownReport(): string {
     const [start, end] = getReportDateRange();
     const title = reportTimeRange.value === 'daily' ? '日报' :
@@ -34,7 +42,7 @@ export function useReportExport(
       md += `- 完成任务数：**${todos.length}** 个\n`;
       md += `- 工作量占比：**${percentage}%**\n\n**任务列表：**\n\n`;
       todos.forEach((todo, index) => {
-        md += `${index + 1}. ✅ ${todo.title}\n`;
+        md += `${index + 1}. ✅ ${describeTodo(todo)}\n`;
       });
       md += '\n';
     });
@@ -65,7 +73,7 @@ export function useReportExport(
       text += `  完成任务数: ${todos.length} 个\n`;
       text += `  工作量占比: ${percentage}%\n  任务列表:\n`;
       todos.forEach((todo, index) => {
-        text += `    ${index + 1}. ${todo.title}\n`;
+        text += `    ${index + 1}. ${describeTodo(todo)}\n`;
       });
       text += '\n';
     });
@@ -5,9 +5,12 @@ import "virtual:uno.css";
 import "@unocss/reset/tailwind.css";
 import "./styles/global.css";
 import "./utils/echarts";  // 初始化 ECharts 组件
+import { reloadOnWorkspaceChange } from "./utils/workspace";
 
 const app = createApp(App);
 const pinia = createPinia();
 
 app.use(pinia);
 app.mount("#app");
+
+reloadOnWorkspaceChange();
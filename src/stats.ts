@@ -4,6 +4,7 @@ import { NMessageProvider, NConfigProvider, darkTheme } from 'naive-ui';
 import StatsView from './views/StatsView.vue';
 import 'uno.css';
 import './styles/global.css';
+import { reloadOnWorkspaceChange } from './utils/workspace';
 
 const pinia = createPinia();
 const app = createApp({
@@ -18,3 +19,5 @@ const app = createApp({
 
 app.use(pinia);
 app.mount('#app');
+
+reloadOnWorkspaceChange();
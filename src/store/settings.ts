@@ -0,0 +1,72 @@
+import { defineStore } from 'pinia';
+import type { Settings, SettingsPatch } from '../types/settings';
+import { settingsApi } from '../api/settings';
+import { isTauri } from '../api/client';
+import { logger } from '../utils/logger';
+import { ErrorHandler } from '../utils/error-handler';
+
+interface SettingsState {
+  settings: Settings;
+  loaded: boolean;
+}
+
+const DEFAULT_SETTINGS: Settings = {
+  version: 2,
+  shortcuts: { quick_add: 'CommandOrControl+Shift+N' },
+  syncIntervalSecs: 60,
+  defaultBroker: '',
+};
+
+let unlistenChanges: (() => void) | null = null;
+
+export const useSettingsStore = defineStore('settings', {
+  state: (): SettingsState => ({
+    settings: { ...DEFAULT_SETTINGS },
+    loaded: false,
+  }),
+
+  actions: {
+    async fetchSettings() {
+      if (!isTauri()) {
+        return;
+      }
+      try {
+        this.settings = await settingsApi.get();
+        this.loaded = true;
+      } catch (error) {
+        logger.error('Failed to fetch settings', { context: 'SettingsStore', data: error });
+      }
+    },
+
+    async updateSettings(patch: SettingsPatch) {
+      try {
+        this.settings = await settingsApi.update(patch);
+        logger.info('Settings updated', { context: 'SettingsStore', data: patch });
+      } catch (error) {
+        ErrorHandler.handle(error, 'SettingsStore', '保存设置失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 监听其他窗口的设置修改，`onChange` 在本地状态更新后调用
+     */
+    async listenChanges(onChange?: (settings: Settings) => void) {
+      if (!isTauri() || unlistenChanges) {
+        return;
+      }
+      const { listen } = await import('@tauri-apps/api/event');
+      unlistenChanges = await listen<Settings>('settings-changed', (event) => {
+        this.settings = event.payload;
+        onChange?.(event.payload);
+      });
+    },
+
+    stopListening() {
+      if (unlistenChanges) {
+        unlistenChanges();
+        unlistenChanges = null;
+      }
+    },
+  },
+});
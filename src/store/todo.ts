@@ -1,5 +1,5 @@
 import { defineStore } from 'pinia';
-import type { Todo, CreateTodoInput, UpdateTodoInput } from '../types/todo';
+import type { Todo, CreateTodoInput, UpdateTodoInput, TodosChanged, TodoPatch, TodoSeries, CreateSeriesInput, UpdateSeriesInput, Tag, CustomField, FieldFilter } from '../types/todo';
 import { logger } from '../utils/logger';
 import { todoApi } from '../api/todo';
 import { ErrorHandler } from '../utils/error-handler';
@@ -7,26 +7,41 @@ import { isTauri } from '../api/client';
 
 interface TodoState {
   todos: Todo[];
+  series: TodoSeries[];
+  tags: Tag[];
+  /** 任务 id → 标签 id */
+  tagMap: Record<string, string[]>;
+  /** 列表与搜索的标签筛选，为空时不筛选 */
+  tagFilter: string[];
+  /** 列表与搜索的自定义字段筛选，在后端按字段值过滤 */
+  fieldFilter: FieldFilter | null;
+  /** 自定义字段定义（按顺序） */
+  customFields: CustomField[];
   loading: boolean;
   error: string | null;
   syncing: boolean;
   syncError: string | null;
+  authRequired: boolean;
 }
 
-const SYNC_INTERVAL_MS = 60_000;
-const LAST_SYNC_KEY = 'sqd_last_sync';
+const DEFAULT_SYNC_INTERVAL_SECS = 60;
 let syncTimer: number | null = null;
-
-const getLastSync = () => localStorage.getItem(LAST_SYNC_KEY);
-const setLastSync = (value: string) => localStorage.setItem(LAST_SYNC_KEY, value);
+let unlistenChanges: (() => void) | null = null;
 
 export const useTodoStore = defineStore('todo', {
   state: (): TodoState => ({
     todos: [],
+    series: [],
+    tags: [],
+    tagMap: {},
+    tagFilter: [],
+    fieldFilter: null,
+    customFields: [],
     loading: false,
     error: null,
     syncing: false,
     syncError: null,
+    authRequired: false,
   }),
 
   getters: {
@@ -41,7 +56,7 @@ export const useTodoStore = defineStore('todo', {
       this.loading = true;
       this.error = null;
       try {
-        this.todos = await todoApi.getAll();
+        this.todos = await todoApi.getAll(this.tagFilter, this.fieldFilter);
         logger.info(`Todos fetched successfully`, { context: 'TodoStore', data: { count: this.todos.length } });
       } catch (error) {
         this.error = ErrorHandler.handle(error, 'TodoStore', '获取任务列表失败');
@@ -56,7 +71,10 @@ export const useTodoStore = defineStore('todo', {
       this.error = null;
       try {
         const todo = await todoApi.create(input);
-        this.todos.push(todo);
+        // todos-changed 事件可能先于返回值到达
+        if (!this.todos.some((t) => t.id === todo.id)) {
+          this.todos.push(todo);
+        }
         logger.info('Todo created successfully', { context: 'TodoStore', data: { id: todo.id } });
         await this.syncNow();
         return todo;
@@ -72,14 +90,21 @@ export const useTodoStore = defineStore('todo', {
       logger.info(`Updating todo ${id}...`, { context: 'TodoStore', data: input });
       this.loading = true;
       this.error = null;
+      // 默认以本地看到的版本做并发校验，调用方可显式覆盖
+      const expectedVersion = input.expectedVersion ?? this.todos.find(t => t.id === id)?.version;
       try {
-        const updatedTodo = await todoApi.update(id, input);
+        const updatedTodo = await todoApi.update(id, { ...input, expectedVersion });
         // 使用 map 优化数组更新
         this.todos = this.todos.map(t => t.id === id ? updatedTodo : t);
         logger.info(`Todo ${id} updated successfully`, { context: 'TodoStore' });
         await this.syncNow();
         return updatedTodo;
       } catch (error) {
+        // 冲突时先换成最新的行，调用方再决定覆盖还是放弃
+        const current = ErrorHandler.conflictTodo(error);
+        if (current) {
+          this.todos = this.todos.map(t => t.id === id ? current : t);
+        }
         this.error = ErrorHandler.handle(error, 'TodoStore', '更新任务失败');
         throw error;
       } finally {
@@ -104,12 +129,255 @@ export const useTodoStore = defineStore('todo', {
       }
     },
 
+    /**
+     * 批量更新，成功的行就地更新，返回逐个结果
+     */
+    async bulkUpdateTodos(ids: string[], patch: TodoPatch) {
+      logger.info(`Bulk updating ${ids.length} todos...`, { context: 'TodoStore', data: patch });
+      this.loading = true;
+      this.error = null;
+      try {
+        const results = await todoApi.bulkUpdate(ids, patch);
+        const updated = results.flatMap((r) => (r.todo ? [r.todo] : []));
+        this.applyChanges({ created: [], updated, deleted: [] });
+        await this.syncNow();
+        return results;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '批量更新失败');
+        throw error;
+      } finally {
+        this.loading = false;
+      }
+    },
+
+    /**
+     * 批量删除，返回逐个结果
+     */
+    async bulkDeleteTodos(ids: string[]) {
+      logger.info(`Bulk deleting ${ids.length} todos...`, { context: 'TodoStore' });
+      this.loading = true;
+      this.error = null;
+      try {
+        const results = await todoApi.bulkDelete(ids);
+        const deleted = results.filter((r) => r.todo).map((r) => r.todoId);
+        this.applyChanges({ created: [], updated: [], deleted });
+        await this.syncNow();
+        return results;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '批量删除失败');
+        throw error;
+      } finally {
+        this.loading = false;
+      }
+    },
+
+    /**
+     * 刷新标签列表与任务标签（标签只在桌面端可用）
+     */
+    async fetchTags() {
+      if (!isTauri()) {
+        return;
+      }
+      try {
+        const [tags, tagMap] = await Promise.all([todoApi.listTags(), todoApi.getTodoTagMap()]);
+        this.tags = tags;
+        this.tagMap = tagMap;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '获取标签失败');
+      }
+    },
+
+    /**
+     * 设置任务的全部标签
+     */
+    async setTodoTags(todoId: string, tagIds: string[]) {
+      this.error = null;
+      try {
+        this.tagMap = { ...this.tagMap, [todoId]: await todoApi.setTodoTags(todoId, tagIds) };
+        this.tags = await todoApi.listTags();
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '设置标签失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 刷新自定义字段定义（只在桌面端可用）
+     */
+    async fetchCustomFields() {
+      if (!isTauri()) {
+        return;
+      }
+      try {
+        this.customFields = await todoApi.listCustomFields();
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '获取自定义字段失败');
+      }
+    },
+
+    async fetchSeries() {
+      try {
+        this.series = await todoApi.listSeries();
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '获取重复任务失败');
+      }
+    },
+
+    /**
+     * 创建重复任务系列，第一个实例加入列表
+     */
+    async createSeries(input: CreateSeriesInput) {
+      logger.info('Creating todo series...', { context: 'TodoStore', data: input });
+      this.error = null;
+      try {
+        const todo = await todoApi.createSeries(input);
+        this.applyChanges({ created: [todo], updated: [], deleted: [] });
+        await this.fetchSeries();
+        await this.syncNow();
+        return todo;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '创建重复任务失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 修改系列（未完成的实例由 todos-changed 事件更新）
+     */
+    async updateSeries(seriesId: string, input: UpdateSeriesInput) {
+      this.error = null;
+      try {
+        const updated = await todoApi.updateSeries(seriesId, input);
+        this.series = this.series.map((s) => (s.id === seriesId ? updated : s));
+        await this.syncNow();
+        return updated;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '修改重复任务失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 停止系列，`deleteOpen` 为 true 时同时删除未完成的实例
+     */
+    async stopSeries(seriesId: string, deleteOpen = false) {
+      this.error = null;
+      try {
+        const stopped = await todoApi.stopSeries(seriesId, deleteOpen);
+        this.series = this.series.map((s) => (s.id === seriesId ? stopped : s));
+        if (deleteOpen) {
+          const deleted = this.todos
+            .filter((t) => t.series_id === seriesId && t.status !== 'completed')
+            .map((t) => t.id);
+          this.applyChanges({ created: [], updated: [], deleted });
+        }
+        await this.syncNow();
+        return stopped;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '停止重复任务失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 按模板为券商批量创建任务
+     */
+    async instantiateTemplate(templateId: string, broker: string) {
+      logger.info('Instantiating template...', { context: 'TodoStore', data: { templateId, broker } });
+      this.error = null;
+      try {
+        const created = await todoApi.instantiateTemplate(templateId, broker);
+        this.applyChanges({ created, updated: [], deleted: [] });
+        await this.syncNow();
+        return created;
+      } catch (error) {
+        this.error = ErrorHandler.handle(error, 'TodoStore', '按模板创建任务失败');
+        throw error;
+      }
+    },
+
+    /**
+     * 撤销（`redo` 为 true 时重做）最近一次任务操作，返回受影响的任务
+     */
+    async undoTodo(redo = false) {
+      const label = redo ? '重做' : '撤销';
+      this.error = null;
+      try {
+        const todo = redo ? await todoApi.redo() : await todoApi.undo();
+        if (todo) {
+          this.applyChanges(todo.deleted_at
+            ? { created: [], updated: [], deleted: [todo.id] }
+            : { created: [], updated: [todo], deleted: [] });
+          logger.info(`${label} applied to todo ${todo.id}`, { context: 'TodoStore' });
+          await this.syncNow();
+        }
+        return todo;
+      } catch (error) {
+        // 任务已被其他写入修改时换成最新的行
+        const current = ErrorHandler.conflictTodo(error);
+        if (current) {
+          this.applyChanges({ created: [], updated: [current], deleted: [] });
+        }
+        this.error = ErrorHandler.handle(error, 'TodoStore', `${label}失败`);
+        throw error;
+      }
+    },
+
+    /**
+     * 就地应用后端推送的变更（新增/更新/删除）
+     */
+    applyChanges(changes: TodosChanged) {
+      const removed = new Set(changes.deleted);
+      const incoming = new Map([...changes.created, ...changes.updated].map((t) => [t.id, t]));
+
+      const next = this.todos
+        .filter((t) => !removed.has(t.id))
+        .map((t) => incoming.get(t.id) ?? t);
+      const known = new Set(next.map((t) => t.id));
+      incoming.forEach((todo, id) => {
+        if (!known.has(id)) {
+          next.push(todo);
+        }
+      });
+
+      this.todos = next.filter((t) => !t.deleted_at);
+    },
+
+    /**
+     * 监听后端 `todos-changed` 事件，`onChange` 在本地状态更新后调用
+     */
+    async listenChanges(onChange?: (changes: TodosChanged) => void) {
+      if (!isTauri() || unlistenChanges) {
+        return;
+      }
+      const { listen } = await import('@tauri-apps/api/event');
+      unlistenChanges = await listen<TodosChanged>('todos-changed', (event) => {
+        logger.debug('Todos changed', {
+          context: 'TodoStore',
+          data: {
+            created: event.payload.created.length,
+            updated: event.payload.updated.length,
+            deleted: event.payload.deleted.length,
+          },
+        });
+        this.applyChanges(event.payload);
+        onChange?.(event.payload);
+      });
+    },
+
+    stopListening() {
+      if (unlistenChanges) {
+        unlistenChanges();
+        unlistenChanges = null;
+      }
+    },
+
     async searchTodos(query: string) {
       logger.info('Searching todos...', { context: 'TodoStore', data: { query } });
       this.loading = true;
       this.error = null;
       try {
-        this.todos = await todoApi.search(query);
+        this.todos = await todoApi.search(query, this.tagFilter, this.fieldFilter);
         logger.info(`Search completed`, { context: 'TodoStore', data: { count: this.todos.length } });
       } catch (error) {
         this.error = ErrorHandler.handle(error, 'TodoStore', '搜索任务失败');
@@ -127,21 +395,12 @@ export const useTodoStore = defineStore('todo', {
       this.syncError = null;
 
       try {
-        const lastSync = getLastSync();
-        const localChanges = await todoApi.getLocalChanges(lastSync);
-        const response = await todoApi.syncWithServer({
-          last_sync: lastSync,
-          changes: localChanges
-        });
-
-        if (response.changes.length > 0) {
-          await todoApi.applyRemoteChanges(response.changes);
-        }
-
-        setLastSync(response.server_time);
+        const report = await todoApi.syncNow();
+        this.authRequired = false;
         await this.fetchTodos();
-        logger.info('Sync completed', { context: 'TodoStore', data: { changes: response.changes.length } });
+        logger.info('Sync completed', { context: 'TodoStore', data: report });
       } catch (error) {
+        this.authRequired = ErrorHandler.isUnauthorized(error);
         this.syncError = ErrorHandler.handle(error, 'TodoStore', '同步失败');
         logger.warn('Sync failed', { context: 'TodoStore', data: error });
       } finally {
@@ -149,15 +408,33 @@ export const useTodoStore = defineStore('todo', {
       }
     },
 
-    startSync() {
+    /**
+     * 立即同步一次并开始定时同步，间隔为 0 时只同步一次
+     */
+    startSync(intervalSecs: number = DEFAULT_SYNC_INTERVAL_SECS) {
       if (!isTauri() || syncTimer) {
         return;
       }
 
       this.syncNow();
+      if (intervalSecs > 0) {
+        syncTimer = window.setInterval(() => {
+          this.syncNow();
+        }, intervalSecs * 1000);
+      }
+    },
+
+    /**
+     * 按新的间隔重新开始定时同步（不会立即同步）
+     */
+    restartSyncTimer(intervalSecs: number) {
+      this.stopSync();
+      if (!isTauri() || intervalSecs <= 0) {
+        return;
+      }
       syncTimer = window.setInterval(() => {
         this.syncNow();
-      }, SYNC_INTERVAL_MS);
+      }, intervalSecs * 1000);
     },
 
     stopSync() {
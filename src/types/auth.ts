@@ -0,0 +1,5 @@
+export interface AuthStatus {
+  loggedIn: boolean;
+  username: string | null;
+  expiresAt: string | null;
+}
@@ -0,0 +1,23 @@
+export interface Settings {
+  version: number;
+  /** 全局快捷键：动作 id（如 `quick_add`）→ 快捷键 */
+  shortcuts: Record<string, string>;
+  /** 自动同步间隔（秒），0 表示关闭 */
+  syncIntervalSecs: number;
+  /** 新建任务默认券商，空字符串表示沿用上次选择 */
+  defaultBroker: string;
+}
+
+export type SettingsPatch = Partial<Omit<Settings, 'version'>>;
+
+export interface ShortcutAction {
+  id: string;
+  label: string;
+}
+
+export interface ShortcutStatus {
+  action: string;
+  shortcut: string;
+  registered: boolean;
+  error: string | null;
+}
@@ -7,8 +7,23 @@ export interface Todo {
   updated_at: string;
   conclusion: string | null;
   deleted_at?: string | null;
+  restored_at?: string | null;
+  restored_by?: string | null;
+  /** 本地写入版本号，更新时作为 expectedVersion 回传 */
+  version: number;
+  /** 所属重复任务系列 */
+  series_id?: string | null;
+  /** 到期日期（YYYY-MM-DD），重复任务的实例才有 */
+  due_date?: string | null;
+  /** 检查项总数与已完成数（仅本地） */
+  items_total?: number;
+  items_done?: number;
+  /** 自定义字段值：字段标识 → 值（数字字段为 number，其他为 string） */
+  custom_fields?: CustomFieldValues;
 }
 
+export type CustomFieldValues = Record<string, string | number>;
+
 export type TodoStatus = "pending" | "in_progress" | "completed";
 
 export interface CreateTodoInput {
@@ -16,11 +31,234 @@ export interface CreateTodoInput {
   status: TodoStatus;
   broker: string;
   conclusion?: string;
+  customFields?: CustomFieldValues;
+  /** 审计日志中记录的来源，默认 ui */
+  source?: ChangeSource;
 }
 
+/** 写操作来源（审计日志） */
+export type ChangeSource = "ui" | "quick-add" | "sync" | "import" | "cli" | "recurrence";
+
 export interface UpdateTodoInput {
   title?: string;
   status?: TodoStatus;
   broker?: string;
   conclusion?: string;
+  /** 要修改的字段值，未提供的字段不变，null 表示清除 */
+  customFields?: Record<string, string | number | null>;
+  /** 调用方看到的版本号，已被其他写入修改时后端返回冲突 */
+  expectedVersion?: number;
+}
+
+/** 批量更新的修改内容，未提供的字段保持不变 */
+export interface TodoPatch {
+  title?: string;
+  status?: TodoStatus;
+  broker?: string;
+  conclusion?: string;
+}
+
+/** 批量操作中单个任务的结果 */
+export interface BulkItemResult {
+  todoId: string;
+  todo: Todo | null;
+  error: string | null;
+}
+
+export interface UpsertSummary {
+  inserted: number;
+  updated: number;
+  skipped: number;
+}
+
+export interface PurgeReport {
+  retentionDays: number;
+  cutoff: string;
+  syncedProfiles: string[];
+  purgedIds: string[];
+}
+
+export interface EmptyTrashReport {
+  purgedIds: string[];
+  /** 配置了同步但删除尚未推送而保留的任务 */
+  keptIds: string[];
+}
+
+export interface SyncReport {
+  pushed: number;
+  pulled: UpsertSummary;
+  serverTime: string;
+}
+
+/** 后端 `todos-changed` 事件载荷 */
+export interface TodosChanged {
+  created: Todo[];
+  updated: Todo[];
+  deleted: string[];
+}
+
+/** 审计日志记录，`before_state` / `after_state` 为 Todo 的 JSON */
+export interface AuditEntry {
+  id: number;
+  todo_id: string;
+  operation: "create" | "update" | "delete" | "restore" | "undo" | "redo";
+  source: ChangeSource;
+  device_id: string;
+  actor: string;
+  before_state: string | null;
+  after_state: string;
+  created_at: string;
+}
+
+/** 任务历史的一页（按时间倒序） */
+export interface TodoHistoryPage {
+  items: AuditEntry[];
+  total: number;
+  page: number;
+  pageSize: number;
+}
+
+/** 导出审计日志的筛选条件（ISO 8601 UTC） */
+export interface AuditExportInput {
+  since?: string;
+  until?: string;
+  todoId?: string;
+}
+
+/** 重复规则，weekdays 中 1 = 周一 … 7 = 周日 */
+export type RecurrenceRule =
+  | { freq: "daily"; interval: number }
+  | { freq: "weekly"; weekdays: number[] }
+  | { freq: "monthlyDay"; day: number }
+  | { freq: "monthlyLastWorkingDay" };
+
+/** 重复任务系列 */
+export interface TodoSeries {
+  id: string;
+  title: string;
+  broker: string;
+  rule: RecurrenceRule;
+  /** 下一个实例的到期日期（YYYY-MM-DD） */
+  nextDue: string;
+  active: boolean;
+  createdAt: string;
+  updatedAt: string;
+  /** 自定义字段值，每个实例都带上 */
+  customFields: CustomFieldValues;
+}
+
+export interface CreateSeriesInput {
+  title: string;
+  broker: string;
+  rule: RecurrenceRule;
+  /** 从哪天开始（YYYY-MM-DD），默认今天 */
+  startDate?: string;
+  customFields?: CustomFieldValues;
+}
+
+/** 修改系列，未提供的字段保持不变；标题、券商和字段值同时应用到未完成的实例 */
+export interface UpdateSeriesInput {
+  title?: string;
+  broker?: string;
+  rule?: RecurrenceRule;
+  /** 要修改的字段值，值为 null 时清除 */
+  customFields?: Record<string, string | number | null>;
+}
+
+/** 任务模板，tasks 为按顺序的任务标题，可含 {broker}、{date} 占位符 */
+export interface TodoTemplate {
+  id: string;
+  name: string;
+  defaultStatus: Exclude<TodoStatus, "completed">;
+  tasks: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface TemplateInput {
+  name: string;
+  defaultStatus: Exclude<TodoStatus, "completed">;
+  tasks: string[];
+}
+
+/** 任务的检查项 */
+export interface TodoItem {
+  id: string;
+  todo_id: string;
+  position: number;
+  content: string;
+  done: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+/** 任务（含最新进度）及其检查项 */
+export interface Checklist {
+  todo: Todo;
+  items: TodoItem[];
+}
+
+/** 任务关联类型（从当前任务看） */
+export type LinkKind = "blocks" | "blocked-by" | "relates-to" | "duplicate-of" | "duplicated-by";
+
+/** 从某个任务看到的一条关联 */
+export interface LinkedTodo {
+  linkId: number;
+  kind: LinkKind;
+  todo: Todo;
+}
+
+/** 任务详情：任务、检查项与关联任务 */
+export interface TodoDetail {
+  todo: Todo;
+  items: TodoItem[];
+  links: LinkedTodo[];
+}
+
+/** 标签（含未删除任务数） */
+export interface Tag {
+  id: string;
+  name: string;
+  color: string | null;
+  todoCount: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** 修改标签：未提供的字段不变，color 为空字符串时清除颜色 */
+export interface UpdateTagInput {
+  name?: string;
+  color?: string;
+}
+
+/** 自定义字段类型 */
+export type FieldType = 'text' | 'number' | 'date' | 'select';
+
+/** 自定义字段定义 */
+export interface CustomField {
+  id: string;
+  /** 字段标识，任务中的值以它为键，创建后不可修改 */
+  key: string;
+  name: string;
+  fieldType: FieldType;
+  /** select 字段的选项 */
+  options: string[];
+  position: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** 按一个自定义字段筛选任务，只使用与字段类型对应的条件 */
+export interface FieldFilter {
+  key: string;
+  /** 文本字段：包含（不区分大小写） */
+  contains?: string;
+  /** 选择字段：等于 */
+  equals?: string;
+  /** 数字字段：范围（含两端） */
+  min?: number;
+  max?: number;
+  /** 日期字段：范围（YYYY-MM-DD，含两端） */
+  from?: string;
+  to?: string;
 }
@@ -0,0 +1,10 @@
+export interface Workspace {
+  id: string;
+  name: string;
+  profile: string;
+}
+
+export interface WorkspaceConfig {
+  active_workspace: string;
+  workspaces: Workspace[];
+}
@@ -1,4 +1,33 @@
 import { logger } from './logger';
+import type { Todo } from '../types/todo';
+
+/**
+ * 后端 AppError::Conflict 的结构化形式（Tauri 命令的 reject 值，或同步服务 409 的响应体）
+ */
+export interface ConflictError {
+  kind: 'conflict';
+  message: string;
+  current: Todo;
+}
+
+const isConflictError = (error: unknown): error is ConflictError =>
+  typeof error === 'object'
+  && error !== null
+  && (error as { kind?: unknown }).kind === 'conflict'
+  && typeof (error as { current?: unknown }).current === 'object';
+
+/**
+ * 错误消息：Error 取 message，结构化错误取 message 字段，其余转为字符串
+ */
+const messageOf = (error: unknown): string => {
+  if (error instanceof Error) {
+    return error.message;
+  }
+  if (typeof error === 'object' && error !== null && typeof (error as { message?: unknown }).message === 'string') {
+    return (error as { message: string }).message;
+  }
+  return String(error);
+};
 
 /**
  * 统一的错误处理工具
@@ -13,11 +42,17 @@ export class ErrorHandler {
    * @returns 格式化的错误消息
    */
   static handle(error: unknown, context: string, defaultMessage: string = '操作失败'): string {
-    const errorMsg = error instanceof Error ? error.message : String(error);
+    const errorMsg = messageOf(error);
 
     logger.error(`${context} - ${defaultMessage}`, { data: errorMsg });
 
     // 返回用户友好的错误消息
+    if (errorMsg.startsWith('Unauthorized')) {
+      return '同步需要登录，请重新登录后再试';
+    }
+    if (isConflictError(error)) {
+      return '任务已在其他窗口被修改，请刷新后重试';
+    }
     if (errorMsg.includes('Invalid status')) {
       return '状态值无效，请选择正确的状态';
     }
@@ -37,4 +72,25 @@ export class ErrorHandler {
     // 返回默认错误消息
     return `${defaultMessage}: ${errorMsg}`;
   }
+
+  /**
+   * 是否为后端返回的 AppError::Unauthorized
+   */
+  static isUnauthorized(error: unknown): boolean {
+    return messageOf(error).startsWith('Unauthorized');
+  }
+
+  /**
+   * 是否为后端返回的 AppError::Conflict（任务已被其他写入修改）
+   */
+  static isConflict(error: unknown): boolean {
+    return isConflictError(error);
+  }
+
+  /**
+   * 解析冲突错误中携带的当前任务，非冲突错误返回 null
+   */
+  static conflictTodo(error: unknown): Todo | null {
+    return isConflictError(error) ? error.current : null;
+  }
 }
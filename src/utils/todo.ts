@@ -1,4 +1,4 @@
-import type { TodoStatus } from '../types/todo';
+import type { CustomField, FieldType, LinkKind, RecurrenceRule, Todo, TodoStatus } from '../types/todo';
 
 /**
  * 获取状态对应的图标
@@ -35,3 +35,58 @@ export const getStatusLabel = (status: TodoStatus): string => {
   };
   return labels[status] ?? '未知';
 };
+
+const WEEKDAY_LABELS = ['一', '二', '三', '四', '五', '六', '日'];
+
+/**
+ * 重复规则的中文描述
+ */
+export const describeRule = (rule: RecurrenceRule): string => {
+  switch (rule.freq) {
+    case 'daily':
+      return rule.interval === 1 ? '每天' : `每 ${rule.interval} 天`;
+    case 'weekly':
+      return `每周${[...rule.weekdays].sort().map((d) => WEEKDAY_LABELS[d - 1]).join('、')}`;
+    case 'monthlyDay':
+      return `每月 ${rule.day} 日`;
+    case 'monthlyLastWorkingDay':
+      return '每月最后一个工作日';
+  }
+};
+
+/**
+ * 关联类型的中文标签（从当前任务看）
+ */
+export const LINK_KIND_LABELS: Record<LinkKind, string> = {
+  blocks: '阻塞',
+  'blocked-by': '被阻塞于',
+  'relates-to': '相关',
+  'duplicate-of': '重复于',
+  'duplicated-by': '被重复于',
+};
+
+/**
+ * 自定义字段类型的中文名称
+ */
+export const FIELD_TYPE_LABELS: Record<FieldType, string> = {
+  text: '文本',
+  number: '数字',
+  date: '日期',
+  select: '选择',
+};
+
+/**
+ * 任务已填写的自定义字段（按字段顺序，没有定义的值不显示）
+ */
+export const getFieldEntries = (todo: Todo, fields: CustomField[]) =>
+  fields
+    .filter((field) => todo.custom_fields?.[field.key] !== undefined)
+    .map((field) => ({ field, value: String(todo.custom_fields![field.key]) }));
+
+/**
+ * 导出用的字段文本，如「合同号: A-01；上线日期: 2026-11-01」
+ */
+export const formatFieldValues = (todo: Todo, fields: CustomField[]): string =>
+  getFieldEntries(todo, fields)
+    .map(({ field, value }) => `${field.name}: ${value}`)
+    .join('；');
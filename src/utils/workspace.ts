@@ -0,0 +1,17 @@
+import { isTauri } from '../api/client';
+import { logger } from './logger';
+
+/**
+ * 工作区切换后重新加载当前窗口
+ * 各窗口入口调用一次，保证所有窗口都读取新工作区的数据
+ */
+export async function reloadOnWorkspaceChange() {
+  if (!isTauri()) {
+    return;
+  }
+  const { listen } = await import('@tauri-apps/api/event');
+  await listen<{ id: string; name: string }>('workspace-changed', (event) => {
+    logger.info('Workspace changed, reloading window', { context: 'workspace', data: event.payload });
+    window.location.reload();
+  });
+}